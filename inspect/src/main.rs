@@ -0,0 +1,200 @@
+//! Reads transcripts written by `remotro::net::transcript::Recorder`.
+
+use remotro::net::transcript::{Direction, Record};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: remotro-inspect [OPTIONS] <TRANSCRIPT>
+
+Prints the packets in a transcript, `-` reads from stdin.
+
+Options:
+  --kind <PREFIX>      Only packets whose kind starts with PREFIX
+  --sent               Only packets sent to the game
+  --received           Only packets received from the game
+  --connection <N>     Only packets of connection N
+  --compact            One line per packet, without bodies
+  --summary            Counts and latencies per kind instead of packets";
+
+/// What the command line asks for.
+enum Command {
+    Help,
+    Inspect(Options),
+}
+
+#[derive(Default)]
+struct Options {
+    path: Option<String>,
+    kind: Option<String>,
+    direction: Option<Direction>,
+    connection: Option<u64>,
+    compact: bool,
+    summary: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--kind" => options.kind = Some(args.next().ok_or("--kind needs a prefix")?),
+                "--sent" => options.direction = Some(Direction::Sent),
+                "--received" => options.direction = Some(Direction::Received),
+                "--connection" => {
+                    let n = args.next().ok_or("--connection needs a number")?;
+                    options.connection =
+                        Some(n.parse().map_err(|_| format!("Invalid connection {n}"))?);
+                }
+                "--compact" => options.compact = true,
+                "--summary" => options.summary = true,
+                "-h" | "--help" => return Ok(Command::Help),
+                _ if arg.starts_with("--") => {
+                    return Err(format!("Unknown option {arg}\n\n{USAGE}"));
+                }
+                _ => options.path = Some(arg),
+            }
+        }
+        if options.path.is_none() {
+            return Err(USAGE.to_string());
+        }
+        Ok(Command::Inspect(options))
+    }
+
+    fn matches(&self, record: &Record) -> bool {
+        self.kind
+            .as_ref()
+            .is_none_or(|kind| record.kind.starts_with(kind))
+            && self
+                .direction
+                .is_none_or(|direction| record.direction == direction)
+            && self
+                .connection
+                .is_none_or(|connection| record.connection == connection)
+    }
+}
+
+/// Per kind totals for `--summary`.
+#[derive(Default)]
+struct KindSummary {
+    sent: usize,
+    received: usize,
+    latencies: Vec<f64>,
+}
+
+fn print_record(record: &Record, start: u64, compact: bool) {
+    let arrow = match record.direction {
+        Direction::Sent => "->",
+        Direction::Received => "<-",
+    };
+    let offset = record.timestamp_ms.saturating_sub(start) as f64 / 1000.0;
+    let mut line = format!(
+        "+{offset:>9.3}s  #{} {arrow} {}",
+        record.connection, record.kind
+    );
+    if let Some(id) = record.id {
+        line += &format!(" [id {id}]");
+    }
+    if let Some(latency) = record.latency_ms {
+        line += &format!(" ({latency:.1} ms)");
+    }
+    println!("{line}");
+    if !compact {
+        let body = serde_json::to_string_pretty(&record.body).unwrap_or_default();
+        for body_line in body.lines() {
+            println!("    {body_line}");
+        }
+    }
+}
+
+fn print_summary(summaries: &BTreeMap<String, KindSummary>) {
+    println!(
+        "{:<32} {:>6} {:>9} {:>12} {:>12}",
+        "kind", "sent", "received", "avg latency", "max latency"
+    );
+    for (kind, summary) in summaries {
+        let (avg, max) = if summary.latencies.is_empty() {
+            ("-".to_string(), "-".to_string())
+        } else {
+            let total: f64 = summary.latencies.iter().sum();
+            let max = summary.latencies.iter().copied().fold(0.0, f64::max);
+            (
+                format!("{:.1} ms", total / summary.latencies.len() as f64),
+                format!("{max:.1} ms"),
+            )
+        };
+        println!(
+            "{kind:<32} {:>6} {:>9} {avg:>12} {max:>12}",
+            summary.sent, summary.received
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Command::Inspect(options)) => options,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let path = options.path.as_deref().unwrap_or("-");
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Failed to open {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+
+    let mut start = None;
+    let mut summaries: BTreeMap<String, KindSummary> = BTreeMap::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Failed to read {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("Skipping line {}: {e}", number + 1);
+                continue;
+            }
+        };
+        // Offsets are relative to the start of the transcript, not of the selection.
+        let start = *start.get_or_insert(record.timestamp_ms);
+        if !options.matches(&record) {
+            continue;
+        }
+        if options.summary {
+            let summary = summaries.entry(record.kind.clone()).or_default();
+            match record.direction {
+                Direction::Sent => summary.sent += 1,
+                Direction::Received => summary.received += 1,
+            }
+            summary.latencies.extend(record.latency_ms);
+        } else {
+            print_record(&record, start, options.compact);
+        }
+    }
+    if options.summary {
+        print_summary(&summaries);
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,69 @@
+//! Runs the `remotro-inspect` binary on a small transcript.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const TRANSCRIPT: &str = r#"{"timestamp_ms":1000,"connection":0,"direction":"sent","kind":"screen/get","body":null}
+{"timestamp_ms":1250,"connection":0,"direction":"received","kind":"result/screen/current","body":{"Ok":{"Menu":{"saved_run":null}}},"latency_ms":250.0}
+{"timestamp_ms":2000,"connection":1,"direction":"sent","kind":"screen/get","body":null}
+"#;
+
+/// Runs the binary with `args`, feeding `TRANSCRIPT` on stdin.
+fn inspect(args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_remotro-inspect"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Runs that fail on their arguments exit without reading stdin.
+    let _ = child.stdin.take().unwrap().write_all(TRANSCRIPT.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn help_succeeds() {
+    for flag in ["--help", "-h"] {
+        let output = inspect(&[flag]);
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).starts_with("Usage:"));
+    }
+}
+
+#[test]
+fn bad_arguments_fail() {
+    let cases = [
+        (&[][..], "Usage:"),
+        (&["--bogus", "-"][..], "Unknown option --bogus"),
+        (&["--kind"][..], "--kind needs a prefix"),
+        (&["--connection", "one", "-"][..], "Invalid connection one"),
+    ];
+    for (args, message) in cases {
+        let output = inspect(args);
+        assert!(!output.status.success(), "{args:?}");
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains(message),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn filters_narrow_the_packets() {
+    let output = inspect(&["--compact", "--sent", "--connection", "1", "-"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines, ["+    1.000s  #1 -> screen/get"]);
+}
+
+#[test]
+fn summary_counts_kinds() {
+    let output = inspect(&["--summary", "--kind", "result/", "-"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("result/screen/current"));
+    assert!(!stdout.contains("screen/get"));
+    assert!(stdout.contains("250.0 ms"));
+}
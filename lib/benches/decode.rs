@@ -0,0 +1,86 @@
+//! Compares [`protocol::bench_decode`] against the old approach of round-tripping the
+//! body through a [`serde_json::Value`] to strip its version.
+//!
+//! Run with `cargo bench --features bench --bench decode`.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use remotro::{
+    balatro::deck::{PlayingCard, Rank, Suit},
+    net::protocol::{self, Packet, Response},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Shaped like a play screen: a hand, plus the full deck the HUD sends on every action.
+#[derive(Serialize, Deserialize)]
+struct Table {
+    hand: Vec<PlayingCard>,
+    discarded: Vec<PlayingCard>,
+    deck: Vec<PlayingCard>,
+    score: f64,
+}
+
+impl Response for Table {}
+
+impl Packet for Table {
+    fn kind() -> String {
+        "play/hand".to_string()
+    }
+}
+
+fn deck() -> Vec<PlayingCard> {
+    let mut deck = Vec::new();
+    for suit in [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
+        let mut rank = Rank::Two;
+        for _ in 0..13 {
+            deck.push(PlayingCard {
+                edition: None,
+                enhancement: None,
+                rank,
+                suit,
+                seal: None,
+                debuffed: false,
+            });
+            rank = rank.next();
+        }
+    }
+    deck
+}
+
+fn packet() -> String {
+    let deck = deck();
+    let table = Table {
+        hand: deck[..8].to_vec(),
+        discarded: deck[8..16].to_vec(),
+        deck,
+        score: 1234.0,
+    };
+    let mut body = serde_json::to_value(Ok::<_, String>(table)).unwrap();
+    body.as_object_mut()
+        .unwrap()
+        .insert("version".to_string(), env!("CARGO_PKG_VERSION").into());
+    format!("{}!{}", <Result<Table, String>>::kind(), body)
+}
+
+/// The decoding strategy used before responses were parsed in a single pass.
+fn decode_via_value<R: DeserializeOwned>(packet: &str) -> R {
+    let (_, body) = packet.split_once('!').unwrap();
+    let mut body: serde_json::Value = serde_json::from_str(body).unwrap();
+    body.as_object_mut().unwrap().remove("version");
+    let body = serde_json::to_string(&body).unwrap();
+    serde_json::from_str(&body).unwrap()
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let packet = packet();
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("single_pass", |b| {
+        b.iter(|| protocol::bench_decode::<Result<Table, String>>(black_box(&packet)).unwrap())
+    });
+    group.bench_function("via_value", |b| {
+        b.iter(|| decode_via_value::<Result<Table, String>>(black_box(&packet)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
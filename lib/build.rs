@@ -0,0 +1,244 @@
+//! Compiles `data/content.toml` into lookup tables for `balatro::content`.
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+const DATA: &str = "data/content.toml";
+
+/// The variants of `content::Rarity`, which a joker's `rarity` must name.
+const RARITIES: [&str; 4] = ["Common", "Uncommon", "Rare", "Legendary"];
+
+/// The `balatro_enum!` each section of the data must cover exactly, as
+/// (section, source file, enum).
+const ENUMS: [(&str, &str, &str); 7] = [
+    ("joker", "src/balatro/jokers.rs", "JokerKind"),
+    ("tarot", "src/balatro/consumables.rs", "TarotKind"),
+    ("planet", "src/balatro/consumables.rs", "PlanetKind"),
+    ("spectral", "src/balatro/consumables.rs", "SpectralKind"),
+    ("voucher", "src/balatro/shop.rs", "VoucherKind"),
+    ("tag", "src/balatro/blinds.rs", "Tag"),
+    ("boss", "src/balatro/blinds.rs", "Boss"),
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Content {
+    joker: BTreeMap<String, Joker>,
+    tarot: BTreeMap<String, Card>,
+    planet: BTreeMap<String, Card>,
+    spectral: BTreeMap<String, Card>,
+    voucher: BTreeMap<String, Card>,
+    tag: BTreeMap<String, Tag>,
+    boss: BTreeMap<String, Boss>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Joker {
+    name: String,
+    rarity: String,
+    cost: u32,
+    effect: String,
+    unlock: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Card {
+    name: String,
+    cost: u32,
+    effect: String,
+    unlock: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Tag {
+    name: String,
+    effect: String,
+    min_ante: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Boss {
+    name: String,
+    effect: String,
+    min_ante: u32,
+    size: f64,
+    #[serde(default)]
+    showdown: bool,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={DATA}");
+    let data = std::fs::read_to_string(DATA).expect("content data is readable");
+    let content: Content = toml::from_str(&data).unwrap_or_else(|e| panic!("{DATA}: {e}"));
+
+    let sections: [BTreeSet<&String>; 7] = [
+        content.joker.keys().collect(),
+        content.tarot.keys().collect(),
+        content.planet.keys().collect(),
+        content.spectral.keys().collect(),
+        content.voucher.keys().collect(),
+        content.tag.keys().collect(),
+        content.boss.keys().collect(),
+    ];
+    for ((section, path, name), entries) in ENUMS.into_iter().zip(sections) {
+        println!("cargo:rerun-if-changed={path}");
+        let keys = enum_keys(path, name);
+        let missing: Vec<_> = keys.iter().filter(|key| !entries.contains(key)).collect();
+        let extra: Vec<_> = entries.iter().filter(|key| !keys.contains(**key)).collect();
+        if !missing.is_empty() || !extra.is_empty() {
+            panic!(
+                "{DATA}: [{section}] does not match {name} in {path}: \
+                 missing {missing:?}, unknown {extra:?}"
+            );
+        }
+    }
+
+    for (key, joker) in &content.joker {
+        if !RARITIES.contains(&joker.rarity.as_str()) {
+            panic!(
+                "{DATA}: [joker.{key}] has rarity {:?}, expected one of {RARITIES:?}",
+                joker.rarity
+            );
+        }
+    }
+
+    let mut out = String::new();
+    table(&mut out, "joker", "JokerInfo", &content.joker, |j| {
+        format!(
+            "name: {:?}, rarity: Rarity::{}, cost: {}, effect: {:?}, unlock: {:?}",
+            j.name, j.rarity, j.cost, j.effect, j.unlock
+        )
+    });
+    for (section, cards) in [
+        ("tarot", &content.tarot),
+        ("planet", &content.planet),
+        ("spectral", &content.spectral),
+        ("voucher", &content.voucher),
+    ] {
+        table(&mut out, section, "CardInfo", cards, |c| {
+            format!(
+                "name: {:?}, cost: {}, effect: {:?}, unlock: {:?}",
+                c.name, c.cost, c.effect, c.unlock
+            )
+        });
+    }
+    table(&mut out, "tag", "TagInfo", &content.tag, |t| {
+        format!(
+            "name: {:?}, effect: {:?}, min_ante: {}",
+            t.name, t.effect, t.min_ante
+        )
+    });
+    table(&mut out, "boss", "BossInfo", &content.boss, |b| {
+        format!(
+            "name: {:?}, effect: {:?}, min_ante: {}, size: {:?}, showdown: {}",
+            b.name, b.effect, b.min_ante, b.size, b.showdown
+        )
+    });
+
+    let dir = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    std::fs::write(Path::new(&dir).join("content.rs"), out).expect("OUT_DIR is writable");
+}
+
+/// Writes `fn <section>(key) -> Option<&'static <ty>>` matching each key to its entry.
+fn table<T>(
+    out: &mut String,
+    section: &str,
+    ty: &str,
+    entries: &BTreeMap<String, T>,
+    fields: impl Fn(&T) -> String,
+) {
+    writeln!(out, "fn {section}(key: &str) -> Option<&'static {ty}> {{").unwrap();
+    writeln!(out, "    match key {{").unwrap();
+    for (key, entry) in entries {
+        writeln!(
+            out,
+            "        {key:?} => Some(&{ty} {{ {} }}),",
+            fields(entry)
+        )
+        .unwrap();
+    }
+    writeln!(out, "        _ => None,\n    }}\n}}").unwrap();
+}
+
+/// The keys of every variant of `balatro_enum!(<name> { .. })` in `path`.
+fn enum_keys(path: &str, name: &str) -> BTreeSet<String> {
+    let source = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    let invocation = format!("balatro_enum!({name}");
+    let start = source
+        .match_indices(&invocation)
+        .map(|(start, _)| start + invocation.len())
+        .find(|&end| source[end..].starts_with([' ', '(', '{']))
+        .unwrap_or_else(|| panic!("{path}: no balatro_enum! for {name}"));
+    let mut rest = source[start..].trim_start();
+    // Skip the naming function, if any.
+    if rest.starts_with('(') {
+        rest = skip_group(rest, path, name).trim_start();
+    }
+    rest = rest
+        .strip_prefix('{')
+        .unwrap_or_else(|| panic!("{path}: expected the variants of {name}"));
+
+    // Each variant is `Name`, optionally `{ fields }`, then `= "key"`, and
+    // may be preceded by line comments.
+    let mut keys = BTreeSet::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest);
+            continue;
+        }
+        if rest.starts_with('}') {
+            return keys;
+        }
+        let len = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let variant = &rest[..len];
+        if variant.is_empty() {
+            panic!(
+                "{path}: expected a variant of {name}, found {:?}",
+                first_line(rest)
+            );
+        }
+        rest = rest[len..].trim_start();
+        if rest.starts_with('{') {
+            rest = skip_group(rest, path, name).trim_start();
+        }
+        let key = rest
+            .strip_prefix('=')
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('"'))
+            .and_then(|rest| rest.split_once('"'))
+            .unwrap_or_else(|| panic!("{path}: {name}::{variant} has no key"));
+        keys.insert(key.0.to_string());
+        rest = key.1.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+}
+
+/// Skips the bracketed group `text` starts with, returning what follows it.
+/// Brackets inside string literals are not expected.
+fn skip_group<'a>(text: &'a str, path: &str, name: &str) -> &'a str {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return &text[index + 1..];
+        }
+    }
+    panic!("{path}: balatro_enum! for {name} is not closed");
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
@@ -1,54 +1,66 @@
-use super::{Screen, play::Play};
+use super::{ActionError, Screen, WithScreen, play::Play};
 use crate::balatro::deck::PlayingCard;
-use crate::{balatro::boosters, balatro_enum, net::Connection};
+use crate::{
+    balatro::{ConnectionRef, boosters},
+    balatro_enum,
+};
 use serde::{Deserialize, Serialize};
 
 pub struct SelectBlind<'a> {
     info: protocol::BlindInfo,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl<'a> SelectBlind<'a> {
-    pub async fn select(self) -> Result<Play<'a>, super::Error> {
-        let info = self.connection.request(protocol::SelectBlind).await??;
-        Ok(Play::new(info, self.connection))
+    pub(crate) fn new(info: protocol::BlindInfo, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+
+    pub async fn select(mut self) -> Result<Play<'a>, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::SelectBlind)
+            .await
+            .with_screen(self)?;
+        Ok(Play::new(info, this.connection))
     }
 
-    pub async fn skip(self) -> Result<SkipResult<'a>, super::Error> {
-        let info = self
+    pub async fn skip(mut self) -> Result<SkipResult<'a>, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::SkipBlind::<'a> {
                 _r_marker: std::marker::PhantomData,
             })
-            .await??;
+            .await
+            .with_screen(self)?;
         match info {
             protocol::SkipBlindResult::Select(info) => {
-                Ok(SkipResult::Select(SelectBlind::new(info, self.connection)))
+                Ok(SkipResult::Select(SelectBlind::new(info, this.connection)))
             }
             protocol::SkipBlindResult::Booster(info) => match info {
                 protocol::SkippedBooster::Arcana(info) => {
                     Ok(SkipResult::Booster(boosters::OpenBoosterPack::Arcana(
-                        boosters::OpenArcanaPack::new(info, self.connection),
+                        boosters::OpenArcanaPack::new(info, this.connection),
                     )))
                 }
                 protocol::SkippedBooster::Buffoon(info) => {
                     Ok(SkipResult::Booster(boosters::OpenBoosterPack::Buffoon(
-                        boosters::OpenBuffoonPack::new(info, self.connection),
+                        boosters::OpenBuffoonPack::new(info, this.connection),
                     )))
                 }
                 protocol::SkippedBooster::Celestial(info) => {
                     Ok(SkipResult::Booster(boosters::OpenBoosterPack::Celestial(
-                        boosters::OpenCelestialPack::new(info, self.connection),
+                        boosters::OpenCelestialPack::new(info, this.connection),
                     )))
                 }
                 protocol::SkippedBooster::Spectral(info) => {
                     Ok(SkipResult::Booster(boosters::OpenBoosterPack::Spectral(
-                        boosters::OpenSpectralPack::new(info, self.connection),
+                        boosters::OpenSpectralPack::new(info, this.connection),
                     )))
                 }
                 protocol::SkippedBooster::Standard(info) => {
                     Ok(SkipResult::Booster(boosters::OpenBoosterPack::Standard(
-                        boosters::OpenStandardPack::new(info, self.connection),
+                        boosters::OpenStandardPack::new(info, this.connection),
                     )))
                 }
             },
@@ -70,9 +82,6 @@ impl<'a> SelectBlind<'a> {
 
 impl<'a> Screen<'a> for SelectBlind<'a> {
     type Info = protocol::BlindInfo;
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
     fn name() -> String {
         "blind_select".to_string()
     }
@@ -101,7 +110,7 @@ pub struct BigBlindChoice {
     pub tag: Tag,
 }
 
-balatro_enum!(Tag {
+balatro_enum!(Tag(|kind| kind.info().name) {
     Uncommon = "tag_uncommon",
     Rare = "tag_rare",
     Negative = "tag_negative",
@@ -134,7 +143,7 @@ pub struct BossBlindChoice {
     pub state: BlindState,
     pub chips: f64,
 }
-balatro_enum!(Boss {
+balatro_enum!(Boss(|kind| kind.info().name) {
     TheOx = "bl_ox",
     TheHook = "bl_hook",
     TheMouth = "bl_mouth",
@@ -182,7 +191,7 @@ pub enum SkipResult<'a> {
 
 pub(crate) mod protocol {
     use crate::{
-        balatro::{Screen, boosters, hud::protocol::HudInfo, play::protocol::PlayInfo},
+        balatro::{GameError, Screen, boosters, hud::protocol::HudInfo, play::protocol::PlayInfo},
         net::protocol::{Packet, Request, Response},
     };
     use serde::{Deserialize, Serialize};
@@ -214,7 +223,7 @@ pub(crate) mod protocol {
     pub struct SelectBlind;
 
     impl Request for SelectBlind {
-        type Expect = Result<PlayInfo, String>;
+        type Expect = Result<PlayInfo, GameError>;
     }
 
     impl Packet for SelectBlind {
@@ -252,7 +261,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a> Request for SkipBlind<'a> {
-        type Expect = Result<SkipBlindResult<'a>, String>;
+        type Expect = Result<SkipBlindResult<'a>, GameError>;
     }
 
     impl<'a> Packet for SkipBlind<'a> {
@@ -5,9 +5,8 @@ use super::{
     jokers::Joker,
 };
 use crate::{
-    balatro::Error,
-    balatro_enum,
-    net::{Connection, protocol::Response},
+    balatro::{ActionError, ConnectionRef, WithScreen},
+    net::protocol::Response,
 };
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -56,20 +55,20 @@ macro_rules! impl_hud_generic {
                     &self.info.hud.run_info
                 }
 
-                async fn move_joker(self, from: u32, to: u32) -> Result<Self, crate::balatro::Error> {
-                    let new_info = self
+                async fn move_joker(mut self, from: u32, to: u32) -> Result<Self, crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request(crate::balatro::hud::protocol::MoveJoker { from, to, _marker: std::marker::PhantomData::<&$t<'a, R>> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn sell_joker(self, index: u32) -> Result<Self, crate::balatro::Error> {
-                    let new_info = self
+                async fn sell_joker(mut self, index: u32) -> Result<Self, crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request(crate::balatro::hud::protocol::SellJoker { index, _marker: std::marker::PhantomData::<&$t<'a, R>> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
                 fn consumable_slots(&self) -> u32 {
@@ -80,28 +79,28 @@ macro_rules! impl_hud_generic {
                     &self.info.hud.consumables
                 }
 
-                async fn move_consumable(self, from: u32, to: u32) -> Result<Self, crate::balatro::Error> {
-                    let new_info = self
+                async fn move_consumable(mut self, from: u32, to: u32) -> Result<Self, crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request(crate::balatro::hud::protocol::MoveConsumable { from, to, _marker: std::marker::PhantomData::<&$t<'a, R>> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn use_consumable(self, index: u32) -> Result<Self, crate::balatro::Error> {
-                    let new_info = self
+                async fn use_consumable(mut self, index: u32) -> Result<Self, crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request(crate::balatro::hud::protocol::UseConsumable { index, _marker: std::marker::PhantomData::<&$t<'a, R>> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn sell_consumable(self, index: u32) -> Result<Self, crate::balatro::Error> {
-                    let new_info = self
+                async fn sell_consumable(mut self, index: u32) -> Result<Self, crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request(crate::balatro::hud::protocol::SellConsumable { index, _marker: std::marker::PhantomData::<&$t<'a, R>> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
             }
         )*
@@ -133,15 +132,15 @@ pub trait Open<'a>: Sized + Screen<'a> {
     fn booster(&self) -> BoosterPackKind;
     fn selections_left(&self) -> SelectionsLeft;
     fn options(&self) -> &[Self::Options];
-    async fn select(self, index: u32) -> Result<SelectResult<'a, Self>, Error>;
-    async fn skip(self) -> Result<Self::ReturnTo, Error>;
+    async fn select(self, index: u32) -> Result<SelectResult<'a, Self>, ActionError<Self>>;
+    async fn skip(self) -> Result<Self::ReturnTo, ActionError<Self>>;
 }
 
 #[allow(async_fn_in_trait)]
 pub trait OpenWithHand<'a>: Sized + Open<'a> {
     async fn hand(&self) -> &[BoosterCard];
-    async fn click(self, indices: &[u32]) -> Result<Self, Error>;
-    async fn move_card(self, from: u32, to: u32) -> Result<Self, Error>;
+    async fn click(self, indices: &[u32]) -> Result<Self, ActionError<Self>>;
+    async fn move_card(self, from: u32, to: u32) -> Result<Self, ActionError<Self>>;
 }
 
 #[derive(Serialize, Deserialize)]
@@ -167,29 +166,34 @@ macro_rules! impl_open {
                 &self.info.options
             }
 
-            async fn select(self, index: u32) -> Result<SelectResult<'a, Self>, Error> {
-                let response = self
+            async fn select(
+                mut self,
+                index: u32,
+            ) -> Result<SelectResult<'a, Self>, ActionError<Self>> {
+                let (response, this) = self
                     .connection
                     .request(protocol::OpenSelect::<'a, Self> {
                         index,
                         _marker: std::marker::PhantomData,
                     })
-                    .await??;
+                    .await
+                    .with_screen(self)?;
                 match response {
                     protocol::SelectResult::Again(info) => {
-                        Ok(SelectResult::Again(Self::new(info, self.connection)))
+                        Ok(SelectResult::Again(Self::new(info, this.connection)))
                     }
                     protocol::SelectResult::Done(result) => Ok(SelectResult::Done(result)),
                 }
             }
 
-            async fn skip(self) -> Result<Self::ReturnTo, Error> {
-                let response = self
+            async fn skip(mut self) -> Result<Self::ReturnTo, ActionError<Self>> {
+                let (response, _) = self
                     .connection
                     .request(protocol::BoosterPackSkip::<'a, Self> {
                         _marker: std::marker::PhantomData,
                     })
-                    .await??;
+                    .await
+                    .with_screen(self)?;
                 Ok(response)
             }
         }
@@ -205,27 +209,29 @@ macro_rules! impl_open_with_hand {
                 &self.info.hand
             }
 
-            async fn click(self, indices: &[u32]) -> Result<Self, Error> {
-                let response = self
+            async fn click(mut self, indices: &[u32]) -> Result<Self, ActionError<Self>> {
+                let (response, this) = self
                     .connection
                     .request(protocol::CardBoosterPackClick::<'a, Self> {
                         indices: indices.to_vec(),
                         _marker: std::marker::PhantomData,
                     })
-                    .await??;
-                Ok(Self::new(response, self.connection))
+                    .await
+                    .with_screen(self)?;
+                Ok(Self::new(response, this.connection))
             }
 
-            async fn move_card(self, from: u32, to: u32) -> Result<Self, Error> {
-                let response = self
+            async fn move_card(mut self, from: u32, to: u32) -> Result<Self, ActionError<Self>> {
+                let (response, this) = self
                     .connection
                     .request(protocol::CardBoosterPackMove::<'a, Self> {
                         from,
                         to,
                         _marker: std::marker::PhantomData,
                     })
-                    .await??;
-                Ok(Self::new(response, self.connection))
+                    .await
+                    .with_screen(self)?;
+                Ok(Self::new(response, this.connection))
             }
         }
     };
@@ -241,87 +247,108 @@ pub enum OpenBoosterPack<'a, R: Response + 'a> {
 
 pub struct OpenArcanaPack<'a, R: Response + 'a> {
     info: protocol::OpenWithHandInfo<'a, Self>,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl_open_with_hand!(OpenArcanaPack, TarotOption);
 
+impl<'a, R: Response + 'a> OpenArcanaPack<'a, R> {
+    pub(crate) fn new(
+        info: protocol::OpenWithHandInfo<'a, Self>,
+        connection: ConnectionRef<'a>,
+    ) -> Self {
+        Self { info, connection }
+    }
+}
+
 impl<'a, R: Response + 'a> Screen<'a> for OpenArcanaPack<'a, R> {
     type Info = protocol::OpenWithHandInfo<'a, Self>;
     fn name() -> String {
         format!("{}/open/arcana", R::kind())
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 pub struct OpenBuffoonPack<'a, R: Response + 'a> {
     info: protocol::OpenInfo<'a, Self>,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl_open!(OpenBuffoonPack, Joker);
 
+impl<'a, R: Response + 'a> OpenBuffoonPack<'a, R> {
+    pub(crate) fn new(info: protocol::OpenInfo<'a, Self>, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+}
+
 impl<'a, R: Response + 'a> Screen<'a> for OpenBuffoonPack<'a, R> {
     type Info = protocol::OpenInfo<'a, Self>;
     fn name() -> String {
         format!("{}/open/buffoon", R::kind())
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 pub struct OpenCelestialPack<'a, R: Response + 'a> {
     info: protocol::OpenInfo<'a, Self>,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl_open!(OpenCelestialPack, PlanetOption);
 
+impl<'a, R: Response + 'a> OpenCelestialPack<'a, R> {
+    pub(crate) fn new(info: protocol::OpenInfo<'a, Self>, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+}
+
 impl<'a, R: Response + 'a> Screen<'a> for OpenCelestialPack<'a, R> {
     type Info = protocol::OpenInfo<'a, Self>;
     fn name() -> String {
         format!("{}/open/celestial", R::kind())
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 pub struct OpenSpectralPack<'a, R: Response + 'a> {
     info: protocol::OpenWithHandInfo<'a, Self>,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl_open_with_hand!(OpenSpectralPack, SpectralOption);
 
+impl<'a, R: Response + 'a> OpenSpectralPack<'a, R> {
+    pub(crate) fn new(
+        info: protocol::OpenWithHandInfo<'a, Self>,
+        connection: ConnectionRef<'a>,
+    ) -> Self {
+        Self { info, connection }
+    }
+}
+
 impl<'a, R: Response + 'a> Screen<'a> for OpenSpectralPack<'a, R> {
     type Info = protocol::OpenWithHandInfo<'a, Self>;
     fn name() -> String {
         format!("{}/open/spectral", R::kind())
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 pub struct OpenStandardPack<'a, R: Response + 'a> {
     info: protocol::OpenInfo<'a, Self>,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl_open!(OpenStandardPack, PlayingCard);
 
+impl<'a, R: Response + 'a> OpenStandardPack<'a, R> {
+    pub(crate) fn new(info: protocol::OpenInfo<'a, Self>, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+}
+
 impl<'a, R: Response + 'a> Screen<'a> for OpenStandardPack<'a, R> {
     type Info = protocol::OpenInfo<'a, Self>;
     fn name() -> String {
         format!("{}/open/standard", R::kind())
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 impl_hud_generic!(
@@ -366,7 +393,7 @@ pub enum PlanetOption {
 
 pub(crate) mod protocol {
     use super::{BoosterCard, BoosterPackKind, Open, OpenWithHand, SelectionsLeft};
-    use crate::balatro::hud::protocol::HudInfo;
+    use crate::balatro::{GameError, hud::protocol::HudInfo};
     use crate::net::protocol::{Packet, Request, Response};
     use serde::{Deserialize, Serialize};
 
@@ -410,7 +437,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, B: Open<'a>> Request for OpenSelect<'a, B> {
-        type Expect = Result<SelectResult<'a, B>, String>;
+        type Expect = Result<SelectResult<'a, B>, GameError>;
     }
 
     impl<'a, B: Open<'a>> Packet for OpenSelect<'a, B> {
@@ -439,7 +466,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, B: Open<'a>> Request for BoosterPackSkip<'a, B> {
-        type Expect = Result<B::ReturnTo, String>;
+        type Expect = Result<B::ReturnTo, GameError>;
     }
 
     impl<'a, B: Open<'a>> Packet for BoosterPackSkip<'a, B> {
@@ -455,7 +482,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, B: OpenWithHand<'a>> Request for CardBoosterPackClick<'a, B> {
-        type Expect = Result<OpenWithHandInfo<'a, B>, String>;
+        type Expect = Result<OpenWithHandInfo<'a, B>, GameError>;
     }
 
     impl<'a, B: OpenWithHand<'a>> Packet for CardBoosterPackClick<'a, B> {
@@ -472,7 +499,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, B: OpenWithHand<'a>> Request for CardBoosterPackMove<'a, B> {
-        type Expect = Result<OpenWithHandInfo<'a, B>, String>;
+        type Expect = Result<OpenWithHandInfo<'a, B>, GameError>;
     }
 
     impl<'a, B: OpenWithHand<'a>> Packet for CardBoosterPackMove<'a, B> {
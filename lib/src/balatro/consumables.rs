@@ -1,4 +1,3 @@
-use crate::balatro_enum;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -15,7 +14,7 @@ pub struct PlanetCard {
     pub negative: bool,
 }
 
-balatro_enum!(PlanetKind {
+balatro_enum!(PlanetKind(|kind| kind.info().name) {
     Mercury = "c_mercury",
     Venus = "c_venus",
     Earth = "c_earth",
@@ -37,7 +36,7 @@ pub struct TarotCard {
     pub negative: bool,
 }
 
-balatro_enum!(TarotKind {
+balatro_enum!(TarotKind(|kind| kind.info().name) {
     Fool = "c_fool",
     Magician = "c_magician",
     HighPriestess = "c_high_priestess",
@@ -69,7 +68,7 @@ pub struct SpectralCard {
     pub negative: bool,
 }
 
-balatro_enum!(SpectralKind {
+balatro_enum!(SpectralKind(|kind| kind.info().name) {
     Familiar = "c_familiar",
     Grim = "c_grim",
     Incantation = "c_incantation",
@@ -0,0 +1,124 @@
+//! Static game content: names, costs and effects of jokers, consumables,
+//! vouchers, tags and Boss Blinds.
+//!
+//! The tables are generated at build time from `data/content.toml`, keyed by
+//! the same keys the game sends, and reached through each kind's `info()`.
+//! The build fails unless every kind has exactly one entry, so `info()`
+//! always finds one:
+//!
+//! ```
+//! use remotro::balatro::{content::Rarity, jokers::JokerKind};
+//!
+//! let info = JokerKind::Blueprint.info();
+//! assert_eq!(info.name, "Blueprint");
+//! assert_eq!(info.rarity, Rarity::Rare);
+//! ```
+
+use super::blinds::{Boss, Tag};
+use super::consumables::{Consumable, PlanetKind, SpectralKind, TarotKind};
+use super::jokers::JokerKind;
+use super::shop::VoucherKind;
+
+include!(concat!(env!("OUT_DIR"), "/content.rs"));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct JokerInfo {
+    pub name: &'static str,
+    pub rarity: Rarity,
+    /// Base shop price, before discounts and editions.
+    pub cost: u32,
+    pub effect: &'static str,
+    /// How to unlock the joker, if it starts locked.
+    pub unlock: Option<&'static str>,
+}
+
+/// A Tarot, Planet or Spectral card, or a voucher.
+#[derive(Debug, PartialEq)]
+pub struct CardInfo {
+    pub name: &'static str,
+    /// Base shop price, before discounts.
+    pub cost: u32,
+    pub effect: &'static str,
+    /// How to unlock the card, if it starts locked.
+    pub unlock: Option<&'static str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TagInfo {
+    pub name: &'static str,
+    pub effect: &'static str,
+    /// The first ante the tag can be offered in.
+    pub min_ante: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BossInfo {
+    pub name: &'static str,
+    pub effect: &'static str,
+    /// The first ante the boss can appear in.
+    pub min_ante: u32,
+    /// Chips needed to beat the blind, in multiples of the ante's base.
+    pub size: f64,
+    /// Whether the boss only appears as the final blind of every 8th ante.
+    pub showdown: bool,
+}
+
+impl JokerKind {
+    pub fn info(&self) -> &'static JokerInfo {
+        joker(self.key()).expect("every joker has content")
+    }
+}
+
+impl TarotKind {
+    pub fn info(&self) -> &'static CardInfo {
+        tarot(self.key()).expect("every tarot has content")
+    }
+}
+
+impl PlanetKind {
+    pub fn info(&self) -> &'static CardInfo {
+        planet(self.key()).expect("every planet has content")
+    }
+}
+
+impl SpectralKind {
+    pub fn info(&self) -> &'static CardInfo {
+        spectral(self.key()).expect("every spectral has content")
+    }
+}
+
+impl Consumable {
+    pub fn info(&self) -> &'static CardInfo {
+        match self {
+            Consumable::Planet(card) => card.kind.info(),
+            Consumable::Tarot(card) => card.kind.info(),
+            Consumable::Spectral(card) => card.kind.info(),
+        }
+    }
+}
+
+impl VoucherKind {
+    pub fn info(&self) -> &'static CardInfo {
+        voucher(self.key()).expect("every voucher has content")
+    }
+}
+
+impl Tag {
+    pub fn info(&self) -> &'static TagInfo {
+        tag(self.key()).expect("every tag has content")
+    }
+}
+
+impl Boss {
+    pub fn info(&self) -> &'static BossInfo {
+        boss(self.key()).expect("every boss has content")
+    }
+}
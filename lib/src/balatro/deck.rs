@@ -1,8 +1,12 @@
 use self::Rank::*;
+use crate::balatro::{
+    hud::Hud,
+    play::{HandCard, HandRules, Play},
+};
 use crate::balatro_enum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PlayingCard {
     pub edition: Option<CardEdition>,
     pub enhancement: Option<Enhancement>,
@@ -12,6 +16,20 @@ pub struct PlayingCard {
     pub debuffed: bool,
 }
 
+impl PlayingCard {
+    /// A plain card of the given rank and suit: no edition, enhancement or seal.
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self {
+            edition: None,
+            enhancement: None,
+            rank,
+            suit,
+            seal: None,
+            debuffed: false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 #[serde(rename_all = "PascalCase")]
 pub enum Suit {
@@ -91,3 +109,185 @@ balatro_enum!(CardEdition {
     Holographic = "e_holo",
     Polychrome = "e_polychrome"
 });
+
+/// Follows where the cards of the deck are during a round, to work out what is
+/// left in the draw pile and how likely draws are.
+///
+/// The game reports the whole deck, the hand and the discard pile, but not the
+/// draw pile. Feed the tracker every [`Play`] screen of a round with
+/// [`observe`](Self::observe): cards that leave the hand are remembered as
+/// spent until the next round, whether or not the game lists them as
+/// discarded.
+///
+/// ```no_run
+/// # fn run(play: &remotro::balatro::play::Play<'_>) {
+/// use remotro::balatro::deck::{DeckTracker, Suit};
+///
+/// let mut tracker = DeckTracker::new();
+/// tracker.observe(play);
+/// let heart = tracker.probability(3, 1, |card| card.suit == Suit::Hearts);
+/// # }
+/// ```
+///
+/// Face down cards in hand cannot be told apart, so they are still counted as
+/// part of the draw pile.
+#[derive(Debug, Clone, Default)]
+pub struct DeckTracker {
+    round: Option<u32>,
+    hand: Vec<Option<PlayingCard>>,
+    spent: Vec<PlayingCard>,
+    draw_pile: Vec<PlayingCard>,
+}
+
+impl DeckTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the tracker from a play screen.
+    pub fn observe(&mut self, play: &Play<'_>) {
+        self.observe_cards(play.round(), play.deck(), play.hand(), play.discarded());
+    }
+
+    /// Updates the tracker from cards reported some other way, e.g. read back
+    /// from a transcript. `deck` is the whole deck, as in [`Hud::deck`].
+    pub fn observe_cards(
+        &mut self,
+        round: u32,
+        deck: &[PlayingCard],
+        hand: &[HandCard],
+        discarded: &[PlayingCard],
+    ) {
+        if self.round != Some(round) {
+            self.round = Some(round);
+            self.hand.clear();
+            self.spent.clear();
+        }
+
+        // Whatever left the hand since the last screen was played or discarded.
+        let mut left = self.hand.iter().flatten().cloned().collect::<Vec<_>>();
+        for card in hand.iter().filter_map(|card| card.card.as_ref()) {
+            take(&mut left, card);
+        }
+        self.spent.extend(left);
+        // The game's discard pile may list cards the tracker never saw leave.
+        let mut unseen = discarded.to_vec();
+        for card in &self.spent {
+            take(&mut unseen, card);
+        }
+        self.spent.extend(unseen);
+        self.hand = hand.iter().map(|card| card.card.clone()).collect();
+
+        self.draw_pile = deck.to_vec();
+        for card in self.hand.iter().flatten().chain(&self.spent) {
+            take(&mut self.draw_pile, card);
+        }
+    }
+
+    /// Cards still to be drawn this round, in no particular order.
+    pub fn draw_pile(&self) -> &[PlayingCard] {
+        &self.draw_pile
+    }
+
+    /// The hand as last observed, with `None` for face down cards.
+    pub fn hand(&self) -> &[Option<PlayingCard>] {
+        &self.hand
+    }
+
+    /// Cards played or discarded this round.
+    pub fn spent(&self) -> &[PlayingCard] {
+        &self.spent
+    }
+
+    /// Probability that the next `draws` cards include at least `at_least`
+    /// cards that `matches`.
+    pub fn probability(
+        &self,
+        draws: usize,
+        at_least: usize,
+        matches: impl Fn(&PlayingCard) -> bool,
+    ) -> f64 {
+        let hits = self.draw_pile.iter().filter(|card| matches(card)).count();
+        at_least_of(self.draw_pile.len(), hits, draws, at_least)
+    }
+
+    /// For each suit, the probability of holding a Flush in it after
+    /// discarding the cards of the hand at `discard` and drawing as many back.
+    /// Indices are the same as for [`Play::click`]; repeats count once, and
+    /// `None` is returned if any is outside the hand.
+    pub fn flush_odds(&self, discard: &[u32], rules: HandRules) -> Option<[(Suit, f64); 4]> {
+        let mut discard = discard.to_vec();
+        discard.sort_unstable();
+        discard.dedup();
+        if discard
+            .last()
+            .is_some_and(|&index| index as usize >= self.hand.len())
+        {
+            return None;
+        }
+        let needed: usize = if rules.four_fingers { 4 } else { 5 };
+        let kept: Vec<_> = self
+            .hand
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| discard.binary_search(&(*index as u32)).is_err())
+            .filter_map(|(_, card)| card.as_ref())
+            .collect();
+        Some(
+            [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds].map(|suit| {
+                let held = kept
+                    .iter()
+                    .filter(|card| rules.has_suit(card, suit))
+                    .count();
+                let hits = self
+                    .draw_pile
+                    .iter()
+                    .filter(|card| rules.has_suit(card, suit))
+                    .count();
+                let missing = needed.saturating_sub(held);
+                let odds = at_least_of(self.draw_pile.len(), hits, discard.len(), missing);
+                (suit, odds)
+            }),
+        )
+    }
+
+    /// The expected number of suits a Flush is completed in after discarding
+    /// the cards of the hand at `discard`, as summed from [`flush_odds`](Self::flush_odds).
+    pub fn expected_flush_completions(&self, discard: &[u32], rules: HandRules) -> Option<f64> {
+        let odds = self.flush_odds(discard, rules)?;
+        Some(odds.iter().map(|(_, odds)| odds).sum())
+    }
+}
+
+/// Removes one copy of `card` from `cards`, if there is one. Cards are matched
+/// by rank and suit, as a held card can be debuffed, enhanced or given an
+/// edition between screens.
+fn take(cards: &mut Vec<PlayingCard>, card: &PlayingCard) {
+    if let Some(index) = cards
+        .iter()
+        .position(|c| c.rank == card.rank && c.suit == card.suit)
+    {
+        cards.swap_remove(index);
+    }
+}
+
+/// Hypergeometric probability of at least `at_least` hits when drawing `draws`
+/// cards from `total`, of which `hits` are hits.
+fn at_least_of(total: usize, hits: usize, draws: usize, at_least: usize) -> f64 {
+    let draws = draws.min(total);
+    let exactly =
+        |k: usize| choose(hits, k) * choose(total - hits, draws - k) / choose(total, draws);
+    (at_least..=draws.min(hits))
+        .filter(|&k| draws - k <= total - hits)
+        .map(exactly)
+        .sum::<f64>()
+        .min(1.0)
+}
+
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Why the game refused an action.
+///
+/// The game reports errors as an object with a `code`, and any details the
+/// error carries, e.g. `{"code":"invalid_index","index":7}`. Errors this
+/// version does not recognise, and plain string errors from older versions of
+/// the mod, become [`GameError::Unknown`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "code", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum GameError {
+    /// The item costs more than the money available.
+    NotEnoughMoney,
+    NoHandsLeft,
+    NoDiscardsLeft,
+    /// Playing or discarding needs at least one selected card.
+    NoCardsSelected,
+    /// More cards are selected than can be played, discarded or targeted at once.
+    TooManyCardsSelected,
+    JokerSlotsFull,
+    ConsumableSlotsFull,
+    /// No card, item or slot exists at the given index.
+    InvalidIndex {
+        index: u32,
+    },
+    /// The card cannot be sold, e.g. an Eternal Joker.
+    CannotSell,
+    /// The consumable cannot be used right now, e.g. because it needs
+    /// selected cards to act on.
+    CannotUse,
+    /// There is no saved run to continue.
+    NoSavedRun,
+    /// The game is not on a screen where the action makes sense.
+    WrongScreen,
+    /// The game state has changed since the screen the action was taken from.
+    /// Actions report this as [`Error::StaleScreen`](super::Error::StaleScreen).
+    StaleScreen,
+    #[serde(untagged)]
+    Unknown(UnknownError),
+}
+
+/// A [`GameError`] this version of the client does not understand.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum UnknownError {
+    /// A plain message, as sent by older versions of the mod.
+    Message(String),
+    /// An error code this version does not know.
+    Coded {
+        code: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::NotEnoughMoney => write!(f, "Not enough money"),
+            GameError::NoHandsLeft => write!(f, "No hands left"),
+            GameError::NoDiscardsLeft => write!(f, "No discards left"),
+            GameError::NoCardsSelected => write!(f, "No cards selected"),
+            GameError::TooManyCardsSelected => write!(f, "Too many cards selected"),
+            GameError::JokerSlotsFull => write!(f, "Joker slots are full"),
+            GameError::ConsumableSlotsFull => write!(f, "Consumable slots are full"),
+            GameError::InvalidIndex { index } => write!(f, "Nothing at index {index}"),
+            GameError::CannotSell => write!(f, "Card cannot be sold"),
+            GameError::CannotUse => write!(f, "Consumable cannot be used right now"),
+            GameError::NoSavedRun => write!(f, "No saved run"),
+            GameError::WrongScreen => write!(f, "Not possible on this screen"),
+            GameError::StaleScreen => {
+                write!(f, "Game state has changed since the screen was shown")
+            }
+            GameError::Unknown(UnknownError::Message(message)) => write!(f, "{message}"),
+            GameError::Unknown(UnknownError::Coded {
+                code,
+                message: Some(message),
+            }) => write!(f, "{message} ({code})"),
+            GameError::Unknown(UnknownError::Coded {
+                code,
+                message: None,
+            }) => write!(f, "Game error {code}"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
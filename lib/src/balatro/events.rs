@@ -0,0 +1,101 @@
+use serde::Deserialize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio_stream::{
+    Stream,
+    wrappers::{BroadcastStream, errors::BroadcastStreamRecvError},
+};
+
+use crate::net;
+
+use log::warn;
+
+/// Something that happened in the game without the client asking for it.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum GameEvent {
+    /// The game moved to another screen on its own, e.g. because a human clicked
+    /// in the game window. Any screen held by the client is out of date; call
+    /// [`Balatro::screen`](super::Balatro::screen) to catch up.
+    ScreenChanged(ScreenKind),
+    /// A packet this version of the client does not understand.
+    Unknown { kind: String, body: String },
+}
+
+/// Which screen the game is on, without any of its state.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenKind {
+    Menu,
+    SelectBlind,
+    Play,
+    RoundOverview,
+    Shop,
+    ShopOpen,
+    SkipOpen,
+    GameOver,
+}
+
+impl GameEvent {
+    fn parse(event: net::Event) -> Self {
+        let parsed = match event.kind.as_str() {
+            "event/screen" => serde_json::from_str::<protocol::ScreenChanged>(&event.body)
+                .map(|changed| GameEvent::ScreenChanged(changed.screen)),
+            _ => {
+                return GameEvent::Unknown {
+                    kind: event.kind,
+                    body: event.body,
+                };
+            }
+        };
+        parsed.unwrap_or_else(|e| {
+            warn!("Malformed {} event: {e}", event.kind);
+            GameEvent::Unknown {
+                kind: event.kind,
+                body: event.body,
+            }
+        })
+    }
+}
+
+/// Stream of [`GameEvent`]s, created by [`Balatro::events`](super::Balatro::events).
+///
+/// Ends when the connection closes. If the stream is not polled often enough,
+/// the oldest events are skipped.
+pub struct Events {
+    inner: BroadcastStream<net::Event>,
+}
+
+impl Events {
+    pub(crate) fn new(inner: tokio::sync::broadcast::Receiver<net::Event>) -> Self {
+        Self {
+            inner: BroadcastStream::new(inner),
+        }
+    }
+}
+
+impl Stream for Events {
+    type Item = GameEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<GameEvent>> {
+        loop {
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => return Poll::Ready(Some(GameEvent::parse(event))),
+                Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(skipped)))) => {
+                    warn!("Event stream fell behind, skipped {skipped} events");
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+mod protocol {
+    use super::ScreenKind;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct ScreenChanged {
+        pub screen: ScreenKind,
+    }
+}
@@ -1,5 +1,8 @@
-use super::{Error, consumables::Consumable, jokers::Joker};
-use crate::balatro::play::PokerHandKind::{FiveOfAKind, FlushFive, FlushHouse};
+use super::{ActionError, consumables::Consumable, jokers::Joker};
+use crate::balatro::play::PokerHandKind::{
+    self, FiveOfAKind, Flush, FlushFive, FlushHouse, FourOfAKind, FullHouse, HighCard, Pair,
+    Straight, StraightFlush, ThreeOfAKind, TwoPair,
+};
 use crate::balatro::{
     Screen,
     blinds::{BigBlindChoice, BossBlindChoice, SmallBlindChoice, Tag},
@@ -22,13 +25,13 @@ pub trait Hud<'a>: Sized + Screen<'a> {
     fn jokers(&self) -> &[Joker];
     fn tags(&self) -> &[Tag];
     fn run_info(&self) -> &RunInfo;
-    async fn move_joker(self, from: u32, to: u32) -> Result<Self, Error>;
-    async fn sell_joker(self, index: u32) -> Result<Self, Error>;
+    async fn move_joker(self, from: u32, to: u32) -> Result<Self, ActionError<Self>>;
+    async fn sell_joker(self, index: u32) -> Result<Self, ActionError<Self>>;
     fn consumable_slots(&self) -> u32;
     fn consumables(&self) -> &[Consumable];
-    async fn move_consumable(self, from: u32, to: u32) -> Result<Self, Error>;
-    async fn use_consumable(self, index: u32) -> Result<Self, Error>;
-    async fn sell_consumable(self, index: u32) -> Result<Self, Error>;
+    async fn move_consumable(self, from: u32, to: u32) -> Result<Self, ActionError<Self>>;
+    async fn use_consumable(self, index: u32) -> Result<Self, ActionError<Self>>;
+    async fn sell_consumable(self, index: u32) -> Result<Self, ActionError<Self>>;
 }
 
 #[doc(hidden)]
@@ -77,22 +80,22 @@ macro_rules! impl_hud {
                     &self.info.hud.run_info
                 }
 
-                async fn move_joker(self, from: u32, to: u32) -> Result<Self, $crate::balatro::Error> {
-                    let new_info = self
+                async fn move_joker(mut self, from: u32, to: u32) -> Result<Self, $crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request($crate::balatro::hud::protocol::MoveJoker
                             { from, to, _marker: std::marker::PhantomData::<&$t> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn sell_joker(self, index: u32) -> Result<Self, $crate::balatro::Error> {
-                    let new_info = self
+                async fn sell_joker(mut self, index: u32) -> Result<Self, $crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request($crate::balatro::hud::protocol::SellJoker
                             { index, _marker: std::marker::PhantomData::<&$t> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
                 fn consumable_slots(&self) -> u32 {
@@ -103,28 +106,28 @@ macro_rules! impl_hud {
                     &self.info.hud.consumables
                 }
 
-                async fn move_consumable(self, from: u32, to: u32) -> Result<Self, $crate::balatro::Error> {
-                    let new_info = self
+                async fn move_consumable(mut self, from: u32, to: u32) -> Result<Self, $crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request($crate::balatro::hud::protocol::MoveConsumable { from, to, _marker: std::marker::PhantomData::<&$t> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn use_consumable(self, index: u32) -> Result<Self, $crate::balatro::Error> {
-                    let new_info = self
+                async fn use_consumable(mut self, index: u32) -> Result<Self, $crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request($crate::balatro::hud::protocol::UseConsumable { index, _marker: std::marker::PhantomData::<&$t> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
 
-                async fn sell_consumable(self, index: u32) -> Result<Self, $crate::balatro::Error> {
-                    let new_info = self
+                async fn sell_consumable(mut self, index: u32) -> Result<Self, $crate::balatro::ActionError<Self>> {
+                    let (new_info, this) = self
                         .connection
                         .request($crate::balatro::hud::protocol::SellConsumable { index, _marker: std::marker::PhantomData::<&$t> })
-                        .await??;
-                    Ok(Self::new(new_info, self.connection))
+                        .await.with_screen(self)?;
+                    Ok(Self::new(new_info, this.connection))
                 }
             }
         )*
@@ -155,52 +158,70 @@ pub struct CurrentPokerHands {
     pub flush_house: Option<CurrentPokerHand>,
     pub flush_fives: Option<CurrentPokerHand>,
 }
+impl CurrentPokerHands {
+    /// The poker hand of `kind`, or `None` for a secret hand that has not been
+    /// unlocked yet.
+    pub fn get(&self, kind: PokerHandKind) -> Option<&CurrentPokerHand> {
+        match kind {
+            HighCard => Some(&self.high_card),
+            Pair => Some(&self.pair),
+            TwoPair => Some(&self.two_pair),
+            ThreeOfAKind => Some(&self.three_of_a_kind),
+            Straight => Some(&self.straight),
+            Flush => Some(&self.flush),
+            FullHouse => Some(&self.full_house),
+            FourOfAKind => Some(&self.four_of_a_kind),
+            StraightFlush => Some(&self.straight_flush),
+            FiveOfAKind => self.five_of_a_kind.as_ref(),
+            FlushHouse => self.flush_house.as_ref(),
+            FlushFive => self.flush_fives.as_ref(),
+        }
+    }
+
+    /// The poker hands unlocked so far.
+    pub fn iter(&self) -> impl Iterator<Item = &CurrentPokerHand> {
+        [
+            &self.high_card,
+            &self.pair,
+            &self.two_pair,
+            &self.three_of_a_kind,
+            &self.straight,
+            &self.flush,
+            &self.full_house,
+            &self.four_of_a_kind,
+            &self.straight_flush,
+        ]
+        .into_iter()
+        .chain(self.five_of_a_kind.as_ref())
+        .chain(self.flush_house.as_ref())
+        .chain(self.flush_fives.as_ref())
+    }
+}
+
 impl IntoIterator for CurrentPokerHands {
     type Item = CurrentPokerHand;
     type IntoIter = std::vec::IntoIter<Self::Item>;
     fn into_iter(self) -> Self::IntoIter {
-        vec![
-            self.high_card,
-            self.pair,
-            self.two_pair,
-            self.three_of_a_kind,
-            self.straight,
-            self.flush,
-            self.full_house,
-            self.four_of_a_kind,
-            self.straight_flush,
-            self.five_of_a_kind.unwrap_or(CurrentPokerHand {
-                hand: PokerHand {
-                    kind: FiveOfAKind,
-                    level: 1,
-                    chips: 120,
-                    mult: 12,
-                },
-                played: 0,
-                played_round: 0,
-            }),
-            self.flush_house.unwrap_or(CurrentPokerHand {
-                hand: PokerHand {
-                    kind: FlushHouse,
-                    level: 1,
-                    chips: 140,
-                    mult: 14,
-                },
-                played: 0,
-                played_round: 0,
-            }),
-            self.flush_fives.unwrap_or(CurrentPokerHand {
-                hand: PokerHand {
-                    kind: FlushFive,
-                    level: 1,
-                    chips: 160,
-                    mult: 16,
-                },
-                played: 0,
-                played_round: 0,
-            }),
+        let secret =
+            |hand: Option<CurrentPokerHand>, kind| hand.or_else(|| CurrentPokerHand::secret(kind));
+        [
+            Some(self.high_card),
+            Some(self.pair),
+            Some(self.two_pair),
+            Some(self.three_of_a_kind),
+            Some(self.straight),
+            Some(self.flush),
+            Some(self.full_house),
+            Some(self.four_of_a_kind),
+            Some(self.straight_flush),
+            secret(self.five_of_a_kind, FiveOfAKind),
+            secret(self.flush_house, FlushHouse),
+            secret(self.flush_fives, FlushFive),
         ]
         .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .into_iter()
     }
 }
 
@@ -211,6 +232,32 @@ pub struct CurrentPokerHand {
     pub played_round: u32,
 }
 
+impl CurrentPokerHand {
+    /// A secret hand as it is before it has been unlocked, or `None` for the
+    /// hands that are always unlocked.
+    pub fn secret(kind: PokerHandKind) -> Option<Self> {
+        match kind {
+            FiveOfAKind => Some(Self::locked(kind, 120, 12)),
+            FlushHouse => Some(Self::locked(kind, 140, 14)),
+            FlushFive => Some(Self::locked(kind, 160, 16)),
+            _ => None,
+        }
+    }
+
+    fn locked(kind: PokerHandKind, chips: u64, mult: u64) -> Self {
+        Self {
+            hand: PokerHand {
+                kind,
+                level: 1,
+                chips,
+                mult,
+            },
+            played: 0,
+            played_round: 0,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CurrentBlinds {
     pub small: SmallBlindChoice,
@@ -222,7 +269,8 @@ pub(crate) mod protocol {
     use super::Hud;
     use crate::{
         balatro::{
-            blinds::Tag, consumables::Consumable, deck::PlayingCard, hud::RunInfo, jokers::Joker,
+            GameError, blinds::Tag, consumables::Consumable, deck::PlayingCard, hud::RunInfo,
+            jokers::Joker,
         },
         net::protocol::{Packet, Request, Response},
     };
@@ -260,7 +308,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, S: Hud<'a>> Request for MoveJoker<'a, S> {
-        type Expect = Result<S::Info, String>;
+        type Expect = Result<S::Info, GameError>;
     }
 
     impl<'a, S: Hud<'a>> Packet for MoveJoker<'a, S> {
@@ -276,7 +324,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, S: Hud<'a>> Request for SellJoker<'a, S> {
-        type Expect = Result<S::Info, String>;
+        type Expect = Result<S::Info, GameError>;
     }
 
     impl<'a, S: Hud<'a>> Packet for SellJoker<'a, S> {
@@ -293,7 +341,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, S: Hud<'a>> Request for MoveConsumable<'a, S> {
-        type Expect = Result<S::Info, String>;
+        type Expect = Result<S::Info, GameError>;
     }
 
     impl<'a, S: Hud<'a>> Packet for MoveConsumable<'a, S> {
@@ -309,7 +357,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, S: Hud<'a>> Request for UseConsumable<'a, S> {
-        type Expect = Result<S::Info, String>;
+        type Expect = Result<S::Info, GameError>;
     }
 
     impl<'a, S: Hud<'a>> Packet for UseConsumable<'a, S> {
@@ -325,7 +373,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a, S: Hud<'a>> Request for SellConsumable<'a, S> {
-        type Expect = Result<S::Info, String>;
+        type Expect = Result<S::Info, GameError>;
     }
 
     impl<'a, S: Hud<'a>> Packet for SellConsumable<'a, S> {
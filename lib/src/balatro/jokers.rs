@@ -1,6 +1,5 @@
 use crate::balatro::deck::{Rank, Suit};
 use crate::balatro::play::PokerHandKind;
-use crate::balatro_enum;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,6 +11,19 @@ pub struct Joker {
     pub lifespan: Lifespan,
 }
 
+impl Joker {
+    /// A free Joker of the given kind with no edition or stickers.
+    pub fn new(kind: JokerKind) -> Self {
+        Self {
+            kind,
+            price: 0,
+            edition: None,
+            rental: false,
+            lifespan: Lifespan::Normal,
+        }
+    }
+}
+
 balatro_enum!(JokerEdition {
     Foil = "e_foil",
     Holographic = "e_holo",
@@ -19,7 +31,7 @@ balatro_enum!(JokerEdition {
     Negative = "e_negative"
 });
 
-balatro_enum!(JokerKind {
+balatro_enum!(JokerKind(|kind| kind.info().name) {
     Joker = "j_joker",
     GreedyJoker = "j_greedy_joker",
     LustyJoker = "j_lusty_joker",
@@ -79,7 +91,7 @@ balatro_enum!(JokerKind {
     Faceless = "j_faceless",
     GreenJoker { mult: u64 } = "j_green_joker",
     Superposition = "j_superposition",
-    TodoList { poker_hand: PokerHandKind } = "j_todo_list",
+    TodoList { poker_hand: PokerHandKind = PokerHandKind::HighCard } = "j_todo_list",
     Cavendish { probability: u64 } = "j_cavendish",
     CardSharp = "j_card_sharp",
     RedCard { mult: u64 } = "j_red_card",
@@ -102,7 +114,7 @@ balatro_enum!(JokerKind {
     TurtleBean { hand_size: u64 } = "j_turtle_bean",
     Erosion { mult: u64 } = "j_erosion",
     ReservedParking { probability: u64 } = "j_reserved_parking",
-    Mail { rank: Rank } = "j_mail",
+    Mail { rank: Rank = Rank::Ace } = "j_mail",
     ToTheMoon = "j_to_the_moon",
     Hallucination { probability: u64 } = "j_hallucination",
     FortuneTeller { mult: u64 } = "j_fortune_teller",
@@ -118,11 +130,11 @@ balatro_enum!(JokerKind {
     Flash { mult: u64 } = "j_flash",
     Popcorn { mult: u64 } = "j_popcorn",
     Trousers { mult: u64 } = "j_trousers",
-    Ancient { suit: Suit } = "j_ancient",
+    Ancient { suit: Suit = Suit::Spades } = "j_ancient",
     Ramen { xmult: f64 } = "j_ramen",
     WalkieTalkie = "j_walkie_talkie",
     Selzer { hands_left: u64 } = "j_selzer",
-    Castle { chips: u64, suit: Suit } = "j_castle",
+    Castle { chips: u64, suit: Suit = Suit::Spades } = "j_castle",
     Smiley = "j_smiley",
     Campfire { xmult: f64 } = "j_campfire",
     Ticket = "j_ticket",
@@ -146,7 +158,7 @@ balatro_enum!(JokerKind {
     Wee { chips: u64 } = "j_wee",
     MerryAndy = "j_merry_andy",
     Oops = "j_oops",
-    Idol { rank: Rank, suit: Suit } = "j_idol",
+    Idol { rank: Rank = Rank::Ace, suit: Suit = Suit::Spades } = "j_idol",
     SeeingDouble = "j_seeing_double",
     Matador = "j_matador",
     HitTheRoad { xmult: f64 } = "j_hit_the_road",
@@ -1,23 +1,18 @@
 use crate::{
-    balatro::{
-        CurrentScreen, Screen, blinds, blinds::SelectBlind, boosters, overview, play::Play, shop,
-        shop::Shop,
-    },
+    balatro::{ActionError, ConnectionRef, CurrentScreen, WithScreen, blinds::SelectBlind},
     balatro_enum,
-    net::Connection,
 };
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt::Display;
-use std::str::FromStr;
 
 pub struct Menu<'a> {
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
     info: protocol::MenuInfo,
 }
 
 impl<'a> Menu<'a> {
-    pub(crate) fn new(connection: &'a mut Connection, info: protocol::MenuInfo) -> Self {
+    pub(crate) fn new(connection: ConnectionRef<'a>, info: protocol::MenuInfo) -> Self {
         Self { connection, info }
     }
 
@@ -26,104 +21,26 @@ impl<'a> Menu<'a> {
     }
 
     pub async fn new_run(
-        self,
+        mut self,
         deck: Deck,
         stake: Stake,
         seed: Option<Seed>,
-    ) -> Result<SelectBlind<'a>, super::Error> {
+    ) -> Result<SelectBlind<'a>, ActionError<Self>> {
         let new_run = protocol::StartRun { deck, stake, seed };
-        let blinds = self.connection.request(new_run).await??;
-        Ok(SelectBlind::new(blinds, self.connection))
+        let (blinds, this) = self.connection.request(new_run).await.with_screen(self)?;
+        Ok(SelectBlind::new(blinds, this.connection))
     }
 
-    pub async fn continue_run(self) -> Result<CurrentScreen<'a>, super::Error> {
+    pub async fn continue_run(mut self) -> Result<CurrentScreen<'a>, ActionError<Self>> {
         let continue_run = protocol::ContinueRun::<'a> {
             _r_marker: std::marker::PhantomData,
         };
-        let screen: crate::balatro::protocol::ScreenInfo<'a> =
-            self.connection.request(continue_run).await??;
-        match screen {
-            crate::balatro::protocol::ScreenInfo::SelectBlind(blinds) => Ok(
-                CurrentScreen::SelectBlind(SelectBlind::new(blinds, self.connection)),
-            ),
-            crate::balatro::protocol::ScreenInfo::Play(play) => {
-                Ok(CurrentScreen::Play(Play::new(play, self.connection)))
-            }
-            crate::balatro::protocol::ScreenInfo::RoundOverview(overview) => {
-                Ok(CurrentScreen::RoundOverview(overview::RoundOverview::new(
-                    overview,
-                    self.connection,
-                )))
-            }
-            crate::balatro::protocol::ScreenInfo::Shop(shop) => {
-                Ok(CurrentScreen::Shop(Shop::new(shop, self.connection)))
-            }
-            crate::balatro::protocol::ScreenInfo::Menu(info) => {
-                Ok(CurrentScreen::Menu(Menu::new(self.connection, info)))
-            }
-            crate::balatro::protocol::ScreenInfo::ShopOpen(pack) => match pack {
-                shop::protocol::BoughtBooster::Arcana(info) => {
-                    Ok(CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Arcana(
-                        boosters::OpenArcanaPack::new(info, self.connection),
-                    )))
-                }
-                shop::protocol::BoughtBooster::Buffoon(info) => {
-                    Ok(CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Buffoon(
-                        boosters::OpenBuffoonPack::new(info, self.connection),
-                    )))
-                }
-                shop::protocol::BoughtBooster::Celestial(info) => Ok(CurrentScreen::ShopOpen(
-                    boosters::OpenBoosterPack::Celestial(boosters::OpenCelestialPack::new(
-                        info,
-                        self.connection,
-                    )),
-                )),
-                shop::protocol::BoughtBooster::Spectral(info) => Ok(CurrentScreen::ShopOpen(
-                    boosters::OpenBoosterPack::Spectral(boosters::OpenSpectralPack::new(
-                        info,
-                        self.connection,
-                    )),
-                )),
-                shop::protocol::BoughtBooster::Standard(info) => Ok(CurrentScreen::ShopOpen(
-                    boosters::OpenBoosterPack::Standard(boosters::OpenStandardPack::new(
-                        info,
-                        self.connection,
-                    )),
-                )),
-            },
-            crate::balatro::protocol::ScreenInfo::SkipOpen(pack) => {
-                match pack {
-                    blinds::protocol::SkippedBooster::Arcana(info) => {
-                        Ok(CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Arcana(
-                            boosters::OpenArcanaPack::new(info, self.connection),
-                        )))
-                    }
-                    blinds::protocol::SkippedBooster::Buffoon(info) => {
-                        Ok(CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Buffoon(
-                            boosters::OpenBuffoonPack::new(info, self.connection),
-                        )))
-                    }
-                    blinds::protocol::SkippedBooster::Celestial(info) => Ok(
-                        CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Celestial(
-                            boosters::OpenCelestialPack::new(info, self.connection),
-                        )),
-                    ),
-                    blinds::protocol::SkippedBooster::Spectral(info) => Ok(
-                        CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Spectral(
-                            boosters::OpenSpectralPack::new(info, self.connection),
-                        )),
-                    ),
-                    blinds::protocol::SkippedBooster::Standard(info) => Ok(
-                        CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Standard(
-                            boosters::OpenStandardPack::new(info, self.connection),
-                        )),
-                    ),
-                }
-            }
-            crate::balatro::protocol::ScreenInfo::GameOver(overview) => Ok(
-                CurrentScreen::GameOver(overview::GameOverview::new(overview, self.connection)),
-            ),
-        }
+        let (screen, this) = self
+            .connection
+            .request(continue_run)
+            .await
+            .with_screen(self)?;
+        Ok(CurrentScreen::new(screen, this.connection))
     }
 }
 
@@ -147,124 +64,16 @@ balatro_enum! {
     }
 }
 
-#[derive(Serialize_repr, Deserialize_repr, Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum Stake {
-    White = 1,
-    Red = 2,
-    Green = 3,
-    Black = 4,
-    Blue = 5,
-    Purple = 6,
-    Orange = 7,
-    Gold = 8,
-}
-
-impl FromStr for Deck {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().to_lowercase().as_str() {
-            "red" => Ok(Deck::Red),
-            "blue" => Ok(Deck::Blue),
-            "yellow" => Ok(Deck::Yellow),
-            "green" => Ok(Deck::Green),
-            "black" => Ok(Deck::Black),
-            "magic" => Ok(Deck::Magic),
-            "nebula" => Ok(Deck::Nebula),
-            "ghost" => Ok(Deck::Ghost),
-            "abandoned" => Ok(Deck::Abandoned),
-            "checkered" => Ok(Deck::Checkered),
-            "zodiac" => Ok(Deck::Zodiac),
-            "painted" => Ok(Deck::Painted),
-            "anaglyph" => Ok(Deck::Anaglyph),
-            "plasma" => Ok(Deck::Plasma),
-            "erratic" => Ok(Deck::Erratic),
-            _ => Err(format!(
-                "Invalid deck. Valid options are: {}",
-                [
-                    "Red",
-                    "Blue",
-                    "Yellow",
-                    "Green",
-                    "Black",
-                    "Magic",
-                    "Nebula",
-                    "Ghost",
-                    "Abandoned",
-                    "Checkered",
-                    "Zodiac",
-                    "Painted",
-                    "Anaglyph",
-                    "Plasma",
-                    "Erratic"
-                ]
-                .join(", ")
-            )),
-        }
-    }
-}
-impl Display for Deck {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            Deck::Red => "Red",
-            Deck::Blue => "Blue",
-            Deck::Yellow => "Yellow",
-            Deck::Green => "Green",
-            Deck::Black => "Black",
-            Deck::Magic => "Magic",
-            Deck::Nebula => "Nebula",
-            Deck::Ghost => "Ghost",
-            Deck::Abandoned => "Abandoned",
-            Deck::Checkered => "Checkered",
-            Deck::Zodiac => "Zodiac",
-            Deck::Painted => "Painted",
-            Deck::Anaglyph => "Anaglyph",
-            Deck::Plasma => "Plasma",
-            Deck::Erratic => "Erratic",
-        }
-        .to_string();
-        write!(f, "{}", str)
-    }
-}
-
-impl FromStr for Stake {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().to_lowercase().as_str() {
-            "white" => Ok(Stake::White),
-            "red" => Ok(Stake::Red),
-            "green" => Ok(Stake::Green),
-            "black" => Ok(Stake::Black),
-            "blue" => Ok(Stake::Blue),
-            "purple" => Ok(Stake::Purple),
-            "orange" => Ok(Stake::Orange),
-            "gold" => Ok(Stake::Gold),
-            _ => Err(format!(
-                "Invalid stake. Valid options are: {}",
-                [
-                    "White", "Red", "Green", "Black", "Blue", "Purple", "Orange", "Gold"
-                ]
-                .join(", ")
-            )),
-        }
-    }
-}
-impl Display for Stake {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            Stake::White => "White",
-            Stake::Red => "Red",
-            Stake::Green => "Green",
-            Stake::Black => "Black",
-            Stake::Blue => "Blue",
-            Stake::Purple => "Purple",
-            Stake::Orange => "Orange",
-            Stake::Gold => "Gold",
-        }
-        .to_string();
-        write!(f, "{}", str)
+balatro_enum! {
+    Stake: u8 {
+        White = 1,
+        Red = 2,
+        Green = 3,
+        Black = 4,
+        Blue = 5,
+        Purple = 6,
+        Orange = 7,
+        Gold = 8,
     }
 }
 
@@ -303,7 +112,7 @@ impl Display for SavedRun {
 pub(crate) mod protocol {
     use super::{Deck, SavedRun, Seed, Stake};
     use crate::{
-        balatro::blinds::protocol::BlindInfo,
+        balatro::{GameError, blinds::protocol::BlindInfo},
         net::protocol::{Packet, Request},
     };
     use serde::{Deserialize, Serialize};
@@ -324,7 +133,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for StartRun {
-        type Expect = Result<BlindInfo, String>;
+        type Expect = Result<BlindInfo, GameError>;
     }
 
     impl Packet for StartRun {
@@ -339,7 +148,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a> Request for ContinueRun<'a> {
-        type Expect = Result<crate::balatro::protocol::ScreenInfo<'a>, String>;
+        type Expect = Result<crate::balatro::protocol::ScreenInfo<'a>, GameError>;
     }
 
     impl<'a> Packet for ContinueRun<'a> {
@@ -1,5 +1,7 @@
 pub mod blinds;
 pub mod deck;
+mod error;
+pub mod events;
 #[doc(hidden)]
 pub mod hud;
 pub mod menu;
@@ -10,8 +12,14 @@ pub mod shop;
 pub mod util;
 pub mod boosters;
 pub mod consumables;
+pub mod content;
 pub mod jokers;
 pub mod overview;
+mod shared;
+
+pub use error::{GameError, UnknownError};
+pub(crate) use shared::ConnectionRef;
+pub use shared::SharedBalatro;
 
 use crate::net::Connection;
 use crate::net::protocol::Response;
@@ -21,11 +29,49 @@ pub struct Balatro {
 }
 
 impl<'a> Balatro {
-    #[doc(hidden)]
+    /// Drives a game over an existing connection. [`Remotro::accept`](crate::Remotro::accept)
+    /// does this for you; use it directly with a custom [`Transport`](crate::net::Transport).
     pub fn new(connection: Connection) -> Self {
         Self { connection }
     }
 
+    /// The game on the other end, as learned in the hello exchange.
+    pub fn peer(&self) -> Option<&crate::net::Peer> {
+        self.connection.peer()
+    }
+
+    /// Whether the connection to the game has ended.
+    pub fn is_closed(&self) -> bool {
+        self.connection.is_closed()
+    }
+
+    /// Completes once the connection to the game has ended, without borrowing
+    /// the [`Balatro`].
+    pub fn closed(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.connection.closed()
+    }
+
+    /// Streams events the game sends on its own, such as screen changes caused
+    /// by a human playing in the game window. Only events sent after this call
+    /// are seen.
+    ///
+    /// ```no_run
+    /// # async fn run(balatro: remotro::balatro::Balatro) {
+    /// use remotro::balatro::events::GameEvent;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let mut events = balatro.events();
+    /// while let Some(event) = events.next().await {
+    ///     if let GameEvent::ScreenChanged(screen) = event {
+    ///         println!("Game moved to {screen:?}");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn events(&self) -> events::Events {
+        events::Events::new(self.connection.events())
+    }
+
     /// Obtains the current state from the connected Balatro game.
     pub async fn screen(&'a mut self) -> Result<CurrentScreen<'a>, Error> {
         let info = self
@@ -34,108 +80,121 @@ impl<'a> Balatro {
                 _r_marker: std::marker::PhantomData,
             })
             .await??;
-        let screen = match info {
+        Ok(CurrentScreen::new(
+            info,
+            ConnectionRef::Borrowed(&mut self.connection),
+        ))
+    }
+}
+
+pub enum CurrentScreen<'a> {
+    /// The Main menu / Title screen, where you can start a new run or load an old run
+    Menu(menu::Menu<'a>),
+    /// The Blind selection, containing the 3 blinds in the ante
+    SelectBlind(blinds::SelectBlind<'a>),
+    /// The playing section when playing against each blind
+    Play(play::Play<'a>),
+    /// The summary of earnings at the end of the blind
+    RoundOverview(overview::RoundOverview<'a>),
+    /// The shop between blinds
+    Shop(shop::Shop<'a>),
+    /// Booster packs opened from the Shop
+    ShopOpen(boosters::OpenBoosterPack<'a, <shop::Shop<'a> as Screen<'a>>::Info>),
+    /// Booster packs opened from skip tags
+    SkipOpen(boosters::OpenBoosterPack<'a, blinds::protocol::SkipBlindResult<'a>>),
+    /// Game Lost (Currently not working from the mod side)
+    GameOver(overview::GameOverview<'a>),
+}
+
+impl<'a> CurrentScreen<'a> {
+    pub(crate) fn new(info: protocol::ScreenInfo<'a>, connection: ConnectionRef<'a>) -> Self {
+        match info {
             protocol::ScreenInfo::Menu(info) => {
-                CurrentScreen::Menu(menu::Menu::new(&mut self.connection, info))
+                CurrentScreen::Menu(menu::Menu::new(connection, info))
             }
             protocol::ScreenInfo::SelectBlind(blinds) => {
-                CurrentScreen::SelectBlind(blinds::SelectBlind::new(blinds, &mut self.connection))
+                CurrentScreen::SelectBlind(blinds::SelectBlind::new(blinds, connection))
             }
             protocol::ScreenInfo::Play(play) => {
-                CurrentScreen::Play(play::Play::new(play, &mut self.connection))
+                CurrentScreen::Play(play::Play::new(play, connection))
+            }
+            protocol::ScreenInfo::RoundOverview(overview) => {
+                CurrentScreen::RoundOverview(overview::RoundOverview::new(overview, connection))
             }
-            protocol::ScreenInfo::RoundOverview(overview) => CurrentScreen::RoundOverview(
-                overview::RoundOverview::new(overview, &mut self.connection),
-            ),
             protocol::ScreenInfo::Shop(shop) => {
-                CurrentScreen::Shop(shop::Shop::new(shop, &mut self.connection))
+                CurrentScreen::Shop(shop::Shop::new(shop, connection))
             }
             protocol::ScreenInfo::ShopOpen(pack) => match pack {
                 shop::protocol::BoughtBooster::Arcana(info) => {
                     CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Arcana(
-                        boosters::OpenArcanaPack::new(info, &mut self.connection),
+                        boosters::OpenArcanaPack::new(info, connection),
                     ))
                 }
                 shop::protocol::BoughtBooster::Buffoon(info) => {
                     CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Buffoon(
-                        boosters::OpenBuffoonPack::new(info, &mut self.connection),
+                        boosters::OpenBuffoonPack::new(info, connection),
                     ))
                 }
                 shop::protocol::BoughtBooster::Celestial(info) => {
                     CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Celestial(
-                        boosters::OpenCelestialPack::new(info, &mut self.connection),
+                        boosters::OpenCelestialPack::new(info, connection),
                     ))
                 }
                 shop::protocol::BoughtBooster::Spectral(info) => {
                     CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Spectral(
-                        boosters::OpenSpectralPack::new(info, &mut self.connection),
+                        boosters::OpenSpectralPack::new(info, connection),
                     ))
                 }
                 shop::protocol::BoughtBooster::Standard(info) => {
                     CurrentScreen::ShopOpen(boosters::OpenBoosterPack::Standard(
-                        boosters::OpenStandardPack::new(info, &mut self.connection),
+                        boosters::OpenStandardPack::new(info, connection),
                     ))
                 }
             },
             protocol::ScreenInfo::SkipOpen(pack) => match pack {
                 blinds::protocol::SkippedBooster::Arcana(info) => {
                     CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Arcana(
-                        boosters::OpenArcanaPack::new(info, &mut self.connection),
+                        boosters::OpenArcanaPack::new(info, connection),
                     ))
                 }
                 blinds::protocol::SkippedBooster::Buffoon(info) => {
                     CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Buffoon(
-                        boosters::OpenBuffoonPack::new(info, &mut self.connection),
+                        boosters::OpenBuffoonPack::new(info, connection),
                     ))
                 }
                 blinds::protocol::SkippedBooster::Celestial(info) => {
                     CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Celestial(
-                        boosters::OpenCelestialPack::new(info, &mut self.connection),
+                        boosters::OpenCelestialPack::new(info, connection),
                     ))
                 }
                 blinds::protocol::SkippedBooster::Spectral(info) => {
                     CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Spectral(
-                        boosters::OpenSpectralPack::new(info, &mut self.connection),
+                        boosters::OpenSpectralPack::new(info, connection),
                     ))
                 }
                 blinds::protocol::SkippedBooster::Standard(info) => {
                     CurrentScreen::SkipOpen(boosters::OpenBoosterPack::Standard(
-                        boosters::OpenStandardPack::new(info, &mut self.connection),
+                        boosters::OpenStandardPack::new(info, connection),
                     ))
                 }
             },
             protocol::ScreenInfo::GameOver(overview) => {
-                CurrentScreen::GameOver(overview::GameOverview::new(overview, &mut self.connection))
+                CurrentScreen::GameOver(overview::GameOverview::new(overview, connection))
             }
-        };
-        Ok(screen)
+        }
     }
 }
 
-pub enum CurrentScreen<'a> {
-    /// The Main menu / Title screen, where you can start a new run or load an old run
-    Menu(menu::Menu<'a>),
-    /// The Blind selection, containing the 3 blinds in the ante
-    SelectBlind(blinds::SelectBlind<'a>),
-    /// The playing section when playing against each blind
-    Play(play::Play<'a>),
-    /// The summary of earnings at the end of the blind
-    RoundOverview(overview::RoundOverview<'a>),
-    /// The shop between blinds
-    Shop(shop::Shop<'a>),
-    /// Booster packs opened from the Shop
-    ShopOpen(boosters::OpenBoosterPack<'a, <shop::Shop<'a> as Screen<'a>>::Info>),
-    /// Booster packs opened from skip tags
-    SkipOpen(boosters::OpenBoosterPack<'a, blinds::protocol::SkipBlindResult<'a>>),
-    /// Game Lost (Currently not working from the mod side)
-    GameOver(overview::GameOverview<'a>),
-}
-
 #[doc(hidden)]
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     Net(crate::net::Error),
-    Game(String),
+    Game(GameError),
+    /// The screen is out of date: the game state has changed since it was
+    /// obtained, either in the game itself or through another screen of the
+    /// same [`SharedBalatro`].
+    StaleScreen,
 }
 
 impl std::fmt::Display for Error {
@@ -152,24 +211,73 @@ impl From<crate::net::Error> for Error {
     }
 }
 
-impl From<String> for Error {
-    fn from(err: String) -> Self {
+impl From<GameError> for Error {
+    fn from(err: GameError) -> Self {
         Error::Game(err)
     }
 }
 
+/// An action that failed, with the screen it was tried on.
+///
+/// Actions consume their screen, so on failure the screen is handed back here
+/// to try something else, e.g. discarding when the game has no hands left.
+/// After a [`Error::Net`] error the screen may no longer match the game.
+pub struct ActionError<S> {
+    pub screen: S,
+    pub error: Error,
+}
+
+impl<S> std::fmt::Debug for ActionError<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> std::fmt::Display for ActionError<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl<S> std::error::Error for ActionError<S> {}
+
+impl<S> From<ActionError<S>> for Error {
+    fn from(err: ActionError<S>) -> Self {
+        err.error
+    }
+}
+
+/// Hands a screen back alongside the outcome of the request made from it.
+pub(crate) trait WithScreen<T> {
+    fn with_screen<S>(self, screen: S) -> Result<(T, S), ActionError<S>>;
+}
+
+impl<T> WithScreen<T> for Result<Result<T, GameError>, Error> {
+    fn with_screen<S>(self, screen: S) -> Result<(T, S), ActionError<S>> {
+        match self {
+            Ok(Ok(response)) => Ok((response, screen)),
+            Ok(Err(error)) => Err(ActionError {
+                screen,
+                error: error.into(),
+            }),
+            Err(error) => Err(ActionError { screen, error }),
+        }
+    }
+}
+
 #[doc(hidden)]
 pub trait Screen<'a> {
     type Info: Response;
     fn name() -> String;
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self;
 }
 
 pub(crate) mod protocol {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        balatro::{menu, overview},
+        balatro::{GameError, menu, overview},
         net::protocol::{Packet, Request, Response},
     };
 
@@ -181,7 +289,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a> Request for GetScreen<'a> {
-        type Expect = Result<ScreenInfo<'a>, String>;
+        type Expect = Result<ScreenInfo<'a>, GameError>;
     }
 
     impl<'a> Packet for GetScreen<'a> {
@@ -4,18 +4,21 @@ use crate::balatro::blinds::CurrentBlind;
 use crate::balatro::deck::PlayingCard;
 use crate::balatro::menu::{self, Menu, Seed};
 use crate::balatro::play::PokerHandKind;
-use crate::balatro::{Error, shop::Shop};
-use crate::net::Connection;
+use crate::balatro::{ActionError, ConnectionRef, WithScreen, shop::Shop};
 
 use super::Screen;
 use super::blinds::Tag;
 use super::jokers::JokerKind;
 pub struct RoundOverview<'a> {
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
     info: protocol::RoundOverviewInfo,
 }
 
 impl<'a> RoundOverview<'a> {
+    pub(crate) fn new(info: protocol::RoundOverviewInfo, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+
     pub fn earnings(&self) -> Vec<Earning> {
         self.info
             .earnings
@@ -41,9 +44,13 @@ impl<'a> RoundOverview<'a> {
         self.info.total_earned
     }
 
-    pub async fn cash_out(self) -> Result<Shop<'a>, Error> {
-        let info = self.connection.request(protocol::CashOut).await??;
-        Ok(Shop::new(info, self.connection))
+    pub async fn cash_out(mut self) -> Result<Shop<'a>, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::CashOut)
+            .await
+            .with_screen(self)?;
+        Ok(Shop::new(info, this.connection))
     }
 }
 
@@ -52,9 +59,6 @@ impl<'a> Screen<'a> for RoundOverview<'a> {
     fn name() -> String {
         "overview".to_string()
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 crate::impl_hud!(RoundOverview);
@@ -76,12 +80,12 @@ pub enum EarningKind {
 }
 
 pub struct GameOverview<'a> {
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
     info: protocol::GameOverviewInfo,
 }
 
 impl<'a> GameOverview<'a> {
-    pub(crate) fn new(info: protocol::GameOverviewInfo, connection: &'a mut Connection) -> Self {
+    pub(crate) fn new(info: protocol::GameOverviewInfo, connection: ConnectionRef<'a>) -> Self {
         Self { connection, info }
     }
 
@@ -148,6 +152,7 @@ pub struct MostPlayedHand {
 pub(crate) mod protocol {
     use crate::{
         balatro::{
+            GameError,
             hud::protocol::HudInfo,
             jokers::JokerKind,
             menu::Seed,
@@ -193,7 +198,7 @@ pub(crate) mod protocol {
     pub struct CashOut;
 
     impl Request for CashOut {
-        type Expect = Result<ShopInfo, String>;
+        type Expect = Result<ShopInfo, GameError>;
     }
 
     impl Packet for CashOut {
@@ -1,20 +1,25 @@
 use serde::{Deserialize, Serialize};
 
+use super::ConnectionRef;
 use super::{
-    Error, Screen,
+    ActionError, Screen, WithScreen,
     blinds::CurrentBlind,
-    deck::PlayingCard,
+    deck::{Enhancement, PlayingCard, Rank, Suit},
+    jokers::{Joker, JokerKind},
     overview::{GameOverview, RoundOverview},
 };
 use crate::balatro_enum;
-use crate::net::Connection;
 
 pub struct Play<'a> {
     info: protocol::PlayInfo,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl<'a> Play<'a> {
+    pub(crate) fn new(info: protocol::PlayInfo, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+
     pub fn blind(&self) -> &CurrentBlind {
         &self.info.current_blind
     }
@@ -39,51 +44,61 @@ impl<'a> Play<'a> {
         self.info.poker_hand.as_ref()
     }
 
-    pub async fn click(self, indices: &[u32]) -> Result<Self, Error> {
-        let info = self
+    pub async fn click(mut self, indices: &[u32]) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::PlayClick {
                 indices: indices.to_vec(),
             })
-            .await??;
-        Ok(Self::new(info, self.connection))
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 
-    pub async fn play(self) -> Result<PlayResult<'a>, Error> {
-        let info = self.connection.request(protocol::PlayPlay).await??;
+    pub async fn play(mut self) -> Result<PlayResult<'a>, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::PlayPlay)
+            .await
+            .with_screen(self)?;
         let result = match info {
             protocol::PlayResult::Again(info) => {
-                PlayResult::Again(Self::new(info, self.connection))
+                PlayResult::Again(Self::new(info, this.connection))
             }
             protocol::PlayResult::RoundOver(info) => {
-                PlayResult::RoundOver(RoundOverview::new(info, self.connection))
+                PlayResult::RoundOver(RoundOverview::new(info, this.connection))
             }
             protocol::PlayResult::GameOver(info) => {
-                PlayResult::GameOver(GameOverview::new(info, self.connection))
+                PlayResult::GameOver(GameOverview::new(info, this.connection))
             }
         };
         Ok(result)
     }
 
-    pub async fn discard(self) -> Result<DiscardResult<'a>, Error> {
-        let info = self.connection.request(protocol::PlayDiscard).await??;
+    pub async fn discard(mut self) -> Result<DiscardResult<'a>, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::PlayDiscard)
+            .await
+            .with_screen(self)?;
         let result = match info {
             protocol::DiscardResult::Again(info) => {
-                DiscardResult::Again(Self::new(info, self.connection))
+                DiscardResult::Again(Self::new(info, this.connection))
             }
             protocol::DiscardResult::GameOver(info) => {
-                DiscardResult::GameOver(Box::new(GameOverview::new(*info, self.connection)))
+                DiscardResult::GameOver(Box::new(GameOverview::new(*info, this.connection)))
             }
         };
         Ok(result)
     }
 
-    pub async fn move_card(self, from: u32, to: u32) -> Result<Self, Error> {
-        let info = self
+    pub async fn move_card(mut self, from: u32, to: u32) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::PlayMove { from, to })
-            .await??;
-        Ok(Self::new(info, self.connection))
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 }
 
@@ -92,9 +107,6 @@ impl<'a> Screen<'a> for Play<'a> {
     fn name() -> String {
         "play".to_string()
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 crate::impl_hud!(Play);
@@ -125,7 +137,7 @@ pub struct PokerHand {
     pub mult: u64,
 }
 
-balatro_enum!(PokerHandKind {
+balatro_enum!(PokerHandKind(Self::key) {
     HighCard = "High Card",
     Pair = "Pair",
     TwoPair = "Two Pair",
@@ -140,10 +152,210 @@ balatro_enum!(PokerHandKind {
     FlushFive = "Flush Five"
 });
 
+impl PokerHandKind {
+    /// Works out the poker hand `cards` make under the standard rules, the way
+    /// the game does when they are selected. Returns `None` for no cards.
+    pub fn evaluate(cards: &[PlayingCard]) -> Option<HandEvaluation> {
+        Self::evaluate_with(cards, HandRules::default())
+    }
+
+    /// Works out the poker hand `cards` make, with the rule changes from
+    /// Jokers like Four Fingers. Returns `None` for no cards.
+    pub fn evaluate_with(cards: &[PlayingCard], rules: HandRules) -> Option<HandEvaluation> {
+        if cards.is_empty() {
+            return None;
+        }
+        let ranked: Vec<usize> = (0..cards.len())
+            .filter(|&i| cards[i].enhancement != Some(Enhancement::Stone))
+            .collect();
+
+        // Cards grouped by rank, largest groups and then highest ranks first.
+        let mut groups: Vec<(Rank, Vec<usize>)> = Vec::new();
+        for &i in &ranked {
+            match groups.iter_mut().find(|(rank, _)| *rank == cards[i].rank) {
+                Some((_, group)) => group.push(i),
+                None => groups.push((cards[i].rank, vec![i])),
+            }
+        }
+        groups.sort_by(|(a, x), (b, y)| y.len().cmp(&x.len()).then(b.cmp(a)));
+        let size = |n: usize| groups.get(n).map_or(0, |(_, group)| group.len());
+        let group = |n: usize| groups[n].1.clone();
+
+        let flush = rules.flush(cards, &ranked);
+        let straight = rules.straight(cards, &ranked);
+        let union = |a: &[usize], b: &[usize]| {
+            let mut union = a.to_vec();
+            union.extend(b.iter().filter(|i| !a.contains(i)));
+            union
+        };
+
+        let (kind, mut scoring) = match (size(0), size(1), &flush, &straight) {
+            (5, _, Some(flush), _) => (PokerHandKind::FlushFive, union(flush, &group(0))),
+            (3, 2, Some(flush), _) => (
+                PokerHandKind::FlushHouse,
+                union(flush, &union(&group(0), &group(1))),
+            ),
+            (5, _, _, _) => (PokerHandKind::FiveOfAKind, group(0)),
+            (_, _, Some(flush), Some(straight)) => {
+                (PokerHandKind::StraightFlush, union(straight, flush))
+            }
+            (4, _, _, _) => (PokerHandKind::FourOfAKind, group(0)),
+            (3, 2, _, _) => (PokerHandKind::FullHouse, union(&group(0), &group(1))),
+            (_, _, Some(flush), _) => (PokerHandKind::Flush, flush.clone()),
+            (_, _, _, Some(straight)) => (PokerHandKind::Straight, straight.clone()),
+            (3, _, _, _) => (PokerHandKind::ThreeOfAKind, group(0)),
+            (2, 2, _, _) => (PokerHandKind::TwoPair, union(&group(0), &group(1))),
+            (2, _, _, _) => (PokerHandKind::Pair, group(0)),
+            // The first of the highest ranked cards, if any are not Stone Cards.
+            _ => (
+                PokerHandKind::HighCard,
+                groups
+                    .first()
+                    .map_or(Vec::new(), |(_, group)| vec![group[0]]),
+            ),
+        };
+        // Stone Cards always score.
+        scoring.extend((0..cards.len()).filter(|i| !ranked.contains(i)));
+        scoring.sort();
+
+        let mut contains = vec![kind];
+        let mut contain = |kind, condition| {
+            if condition && !contains.contains(&kind) {
+                contains.push(kind);
+            }
+        };
+        contain(PokerHandKind::Pair, size(0) >= 2);
+        contain(PokerHandKind::TwoPair, size(0) >= 2 && size(1) >= 2);
+        contain(PokerHandKind::ThreeOfAKind, size(0) >= 3);
+        contain(PokerHandKind::Straight, straight.is_some());
+        contain(PokerHandKind::Flush, flush.is_some());
+        contain(PokerHandKind::FullHouse, size(0) >= 3 && size(1) >= 2);
+        contain(PokerHandKind::FourOfAKind, size(0) >= 4);
+        contain(
+            PokerHandKind::StraightFlush,
+            straight.is_some() && flush.is_some(),
+        );
+        contain(PokerHandKind::FiveOfAKind, size(0) >= 5);
+
+        Some(HandEvaluation {
+            kind,
+            scoring,
+            contains,
+        })
+    }
+}
+
+/// The poker hand some cards make, from [`PokerHandKind::evaluate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandEvaluation {
+    pub kind: PokerHandKind,
+    /// Indices of the cards that score, in order.
+    pub scoring: Vec<usize>,
+    /// Every poker hand the cards contain, starting with `kind`, for Jokers
+    /// like Jolly Joker that trigger on any hand containing a Pair.
+    pub contains: Vec<PokerHandKind>,
+}
+
+/// Changes to how poker hands are made, from Jokers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HandRules {
+    /// Four Fingers: Flushes and Straights can be made with 4 cards.
+    pub four_fingers: bool,
+    /// Shortcut: Straights can skip one rank between cards.
+    pub shortcut: bool,
+    /// Smeared Joker: Hearts and Diamonds count as the same suit, as do Spades
+    /// and Clubs.
+    pub smeared: bool,
+}
+
+impl HandRules {
+    /// The rules in effect with `jokers` in the Joker slots.
+    pub fn from_jokers(jokers: &[Joker]) -> Self {
+        let has = |kind| jokers.iter().any(|joker| joker.kind == kind);
+        Self {
+            four_fingers: has(JokerKind::FourFingers),
+            shortcut: has(JokerKind::Shortcut),
+            smeared: has(JokerKind::Smeared),
+        }
+    }
+
+    /// Whether `card` counts as `suit`. Wild Cards count as every suit and
+    /// Stone Cards as none.
+    pub fn has_suit(&self, card: &PlayingCard, suit: Suit) -> bool {
+        let smeared = |suit| match suit {
+            Suit::Hearts | Suit::Diamonds => Suit::Hearts,
+            Suit::Spades | Suit::Clubs => Suit::Spades,
+        };
+        match card.enhancement {
+            Some(Enhancement::Stone) => false,
+            Some(Enhancement::Wild) => true,
+            _ if self.smeared => smeared(card.suit) == smeared(suit),
+            _ => card.suit == suit,
+        }
+    }
+
+    /// Cards needed for a Flush or a Straight.
+    fn needed(&self) -> usize {
+        if self.four_fingers { 4 } else { 5 }
+    }
+
+    /// The cards making a Flush, if there is one.
+    fn flush(&self, cards: &[PlayingCard], ranked: &[usize]) -> Option<Vec<usize>> {
+        [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds]
+            .into_iter()
+            .map(|suit| {
+                ranked
+                    .iter()
+                    .copied()
+                    .filter(|&i| self.has_suit(&cards[i], suit))
+                    .collect::<Vec<_>>()
+            })
+            .find(|flush| flush.len() >= self.needed())
+    }
+
+    /// The cards making a Straight, if there is one. Aces count high or low,
+    /// but a Straight does not wrap around from King to Two.
+    fn straight(&self, cards: &[PlayingCard], ranked: &[usize]) -> Option<Vec<usize>> {
+        // Two is 2 and so on, with the Ace as both 1 and 14.
+        let value = |rank: Rank| rank as u8 + 2;
+        let mut values: Vec<u8> = ranked.iter().map(|&i| value(cards[i].rank)).collect();
+        if values.contains(&value(Rank::Ace)) {
+            values.push(1);
+        }
+        values.sort();
+        values.dedup();
+
+        let step = if self.shortcut { 2 } else { 1 };
+        let mut best: &[u8] = &[];
+        let mut start = 0;
+        for end in 1..=values.len() {
+            if end == values.len() || values[end] - values[end - 1] > step {
+                if end - start > best.len() {
+                    best = &values[start..end];
+                }
+                start = end;
+            }
+        }
+        if best.len() < self.needed() {
+            return None;
+        }
+        let in_run =
+            |rank: Rank| best.contains(&value(rank)) || (rank == Rank::Ace && best.contains(&1));
+        Some(
+            ranked
+                .iter()
+                .copied()
+                .filter(|&i| in_run(cards[i].rank))
+                .collect(),
+        )
+    }
+}
+
 pub(crate) mod protocol {
     use super::{CurrentBlind, HandCard, PokerHand};
     use crate::{
         balatro::{
+            GameError,
             deck::PlayingCard,
             hud::protocol::HudInfo,
             overview::protocol::{GameOverviewInfo, RoundOverviewInfo},
@@ -177,7 +389,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for PlayClick {
-        type Expect = Result<PlayInfo, String>;
+        type Expect = Result<PlayInfo, GameError>;
     }
 
     impl Packet for PlayClick {
@@ -190,7 +402,7 @@ pub(crate) mod protocol {
     pub struct PlayPlay;
 
     impl Request for PlayPlay {
-        type Expect = Result<PlayResult, String>;
+        type Expect = Result<PlayResult, GameError>;
     }
 
     impl Packet for PlayPlay {
@@ -203,7 +415,7 @@ pub(crate) mod protocol {
     pub struct PlayDiscard;
 
     impl Request for PlayDiscard {
-        type Expect = Result<DiscardResult, String>;
+        type Expect = Result<DiscardResult, GameError>;
     }
 
     impl Packet for PlayDiscard {
@@ -249,7 +461,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for PlayMove {
-        type Expect = Result<PlayInfo, String>;
+        type Expect = Result<PlayInfo, GameError>;
     }
 
     impl Packet for PlayMove {
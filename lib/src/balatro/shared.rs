@@ -0,0 +1,160 @@
+use super::{Balatro, CurrentScreen, Error, GameError, protocol};
+use crate::net::{
+    Connection, Observer, Peer,
+    protocol::{Request, Response},
+};
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// A [`Balatro`] that can be cloned and shared between tasks.
+///
+/// Screens from a shared handle own their connection, so they are `'static`
+/// and `Send`, and can be stored or moved to another task. Only one screen is
+/// current at a time: once an action succeeds, every screen obtained before it
+/// fails with [`Error::StaleScreen`] instead of acting on state the game has
+/// left behind. Reading a screen never fails. The connection stays open
+/// while any clone of the handle, or any screen from it, is alive.
+///
+/// ```no_run
+/// # async fn run(balatro: remotro::balatro::Balatro) -> Result<(), remotro::balatro::Error> {
+/// use remotro::balatro::CurrentScreen;
+///
+/// let balatro = balatro.into_shared();
+/// if let CurrentScreen::Play(play) = balatro.screen().await? {
+///     tokio::spawn(async move { play.click(&[0, 1]).await.is_ok() });
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SharedBalatro {
+    state: Arc<Mutex<SharedState>>,
+    observer: Observer,
+    peer: Option<Peer>,
+}
+
+pub(crate) struct SharedState {
+    connection: Connection,
+    /// Counts successful actions, so screens can tell whether they are current.
+    epoch: u64,
+}
+
+impl Balatro {
+    /// Converts into a handle whose screens are not tied to a borrow of it.
+    pub fn into_shared(self) -> SharedBalatro {
+        SharedBalatro {
+            observer: self.connection.observer(),
+            peer: self.connection.peer().cloned(),
+            state: Arc::new(Mutex::new(SharedState {
+                connection: self.connection,
+                epoch: 0,
+            })),
+        }
+    }
+}
+
+impl SharedBalatro {
+    /// The game on the other end, as learned in the hello exchange.
+    pub fn peer(&self) -> Option<&Peer> {
+        self.peer.as_ref()
+    }
+
+    /// Whether the connection to the game has ended.
+    pub fn is_closed(&self) -> bool {
+        self.observer.is_closed()
+    }
+
+    /// Completes once the connection to the game has ended.
+    pub fn closed(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.observer.closed()
+    }
+
+    /// Streams events the game sends on its own. See [`Balatro::events`].
+    pub fn events(&self) -> super::events::Events {
+        super::events::Events::new(self.observer.events())
+    }
+
+    /// Obtains the current state from the connected Balatro game. Waits for
+    /// any action in flight on another task to finish first.
+    pub async fn screen(&self) -> Result<CurrentScreen<'static>, Error> {
+        let mut state = self.state.lock().await;
+        let info = state
+            .connection
+            .request(protocol::GetScreen::<'static> {
+                _r_marker: std::marker::PhantomData,
+            })
+            .await??;
+        let connection = ConnectionRef::Shared {
+            state: self.state.clone(),
+            epoch: state.epoch,
+            game_epoch: state.connection.epoch(),
+        };
+        Ok(CurrentScreen::new(info, connection))
+    }
+}
+
+/// The connection a screen acts through: borrowed from a [`Balatro`], or
+/// shared through a [`SharedBalatro`].
+pub(crate) enum ConnectionRef<'a> {
+    Borrowed(&'a mut Connection),
+    Shared {
+        state: Arc<Mutex<SharedState>>,
+        /// The epoch of the state this screen shows.
+        epoch: u64,
+        /// The game's own epoch for that state, if it reports them.
+        game_epoch: Option<u64>,
+    },
+}
+
+impl ConnectionRef<'_> {
+    /// Sends an action, echoing the game's epoch for the state the screen
+    /// shows. A shared screen must also be current, and a successful action,
+    /// or the game reporting that it moved on, makes every other screen from
+    /// the same handle stale.
+    pub(crate) async fn request<R, P>(&mut self, req: R) -> Result<Result<P, GameError>, Error>
+    where
+        R: Request<Expect = Result<P, GameError>>,
+        P: Response,
+    {
+        let response = match self {
+            ConnectionRef::Borrowed(connection) => {
+                // A borrowed screen always comes from the latest response.
+                let epoch = connection.epoch();
+                connection.act(req, epoch).await?
+            }
+            ConnectionRef::Shared {
+                state,
+                epoch,
+                game_epoch,
+            } => {
+                let mut state = state.lock().await;
+                if state.epoch != *epoch {
+                    return Err(Error::StaleScreen);
+                }
+                let response = state.connection.act(req, *game_epoch).await?;
+                match &response {
+                    Ok(_) => {
+                        state.epoch += 1;
+                        *epoch = state.epoch;
+                        *game_epoch = state.connection.epoch();
+                    }
+                    // The game moved on without us, so every screen is stale.
+                    Err(GameError::StaleScreen) => {
+                        if state.connection.epoch() != *game_epoch {
+                            state.epoch += 1;
+                        }
+                    }
+                    // The game refused the action, and its error says which
+                    // epoch the screen's state is at now.
+                    Err(_) => *game_epoch = state.connection.epoch(),
+                }
+                response
+            }
+        };
+        match response {
+            Err(GameError::StaleScreen) => Err(Error::StaleScreen),
+            response => Ok(response),
+        }
+    }
+}
@@ -8,18 +8,24 @@ use super::{
     jokers::Joker,
 };
 use crate::{
-    balatro::{Error, blinds::SelectBlind, deck::PlayingCard, shop::protocol::ShopInfo},
+    balatro::{
+        ActionError, ConnectionRef, WithScreen, blinds::SelectBlind, deck::PlayingCard,
+        shop::protocol::ShopInfo,
+    },
     balatro_enum,
-    net::Connection,
 };
 use serde::{Deserialize, Serialize};
 
 pub struct Shop<'a> {
     info: protocol::ShopInfo,
-    connection: &'a mut Connection,
+    connection: ConnectionRef<'a>,
 }
 
 impl<'a> Shop<'a> {
+    pub(crate) fn new(info: protocol::ShopInfo, connection: ConnectionRef<'a>) -> Self {
+        Self { info, connection }
+    }
+
     pub fn main_cards(&self) -> &[MainCard] {
         &self.info.main
     }
@@ -32,65 +38,77 @@ impl<'a> Shop<'a> {
         &self.info.boosters
     }
 
-    pub async fn buy_main(self, index: u8) -> Result<Self, Error> {
-        let info = self
+    pub async fn buy_main(mut self, index: u8) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::ShopBuyMain { index })
-            .await??;
-        Ok(Self::new(info, self.connection))
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 
-    pub async fn buy_and_use(self, index: u8) -> Result<Self, Error> {
-        let info = self
+    pub async fn buy_and_use(mut self, index: u8) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::ShopBuyUse { index })
-            .await??;
-        Ok(Self::new(info, self.connection))
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 
-    pub async fn buy_voucher(self, index: u8) -> Result<Self, Error> {
-        let info = self
+    pub async fn buy_voucher(mut self, index: u8) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::ShopBuyVoucher { index })
-            .await??;
-        Ok(Self::new(info, self.connection))
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 
-    pub async fn buy_booster(self, index: u8) -> Result<BoughtBooster<'a>, Error> {
-        let info = self
+    pub async fn buy_booster(mut self, index: u8) -> Result<BoughtBooster<'a>, ActionError<Self>> {
+        let (info, this) = self
             .connection
             .request(protocol::ShopBuyBooster {
                 index,
                 _r_marker: std::marker::PhantomData,
             })
-            .await??;
+            .await
+            .with_screen(self)?;
         match info {
             protocol::BoughtBooster::Buffoon(info) => Ok(BoughtBooster::Buffoon(
-                OpenBuffoonPack::new(info, self.connection),
+                OpenBuffoonPack::new(info, this.connection),
             )),
             protocol::BoughtBooster::Celestial(info) => Ok(BoughtBooster::Celestial(
-                OpenCelestialPack::new(info, self.connection),
+                OpenCelestialPack::new(info, this.connection),
             )),
             protocol::BoughtBooster::Spectral(info) => Ok(BoughtBooster::Spectral(
-                OpenSpectralPack::new(info, self.connection),
+                OpenSpectralPack::new(info, this.connection),
             )),
             protocol::BoughtBooster::Standard(info) => Ok(BoughtBooster::Standard(
-                OpenStandardPack::new(info, self.connection),
+                OpenStandardPack::new(info, this.connection),
             )),
             protocol::BoughtBooster::Arcana(info) => Ok(BoughtBooster::Arcana(
-                OpenArcanaPack::new(info, self.connection),
+                OpenArcanaPack::new(info, this.connection),
             )),
         }
     }
 
-    pub async fn reroll(self) -> Result<Self, Error> {
-        let info = self.connection.request(protocol::ShopReroll {}).await??;
-        Ok(Self::new(info, self.connection))
+    pub async fn reroll(mut self) -> Result<Self, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::ShopReroll {})
+            .await
+            .with_screen(self)?;
+        Ok(Self::new(info, this.connection))
     }
 
-    pub async fn leave(self) -> Result<SelectBlind<'a>, Error> {
-        let info = self.connection.request(protocol::ShopContinue {}).await??;
-        Ok(SelectBlind::new(info, self.connection))
+    pub async fn leave(mut self) -> Result<SelectBlind<'a>, ActionError<Self>> {
+        let (info, this) = self
+            .connection
+            .request(protocol::ShopContinue {})
+            .await
+            .with_screen(self)?;
+        Ok(SelectBlind::new(info, this.connection))
     }
 }
 
@@ -99,9 +117,6 @@ impl<'a> Screen<'a> for Shop<'a> {
     fn name() -> String {
         "shop".to_string()
     }
-    fn new(info: Self::Info, connection: &'a mut Connection) -> Self {
-        Self { info, connection }
-    }
 }
 
 crate::impl_hud!(Shop);
@@ -135,7 +150,7 @@ pub struct Voucher {
     pub price: u8,
 }
 
-balatro_enum!(VoucherKind {
+balatro_enum!(VoucherKind(|kind| kind.info().name) {
     Blank = "v_blank",
     Antimatter = "v_antimatter",
     ClearanceSale = "v_clearance_sale",
@@ -174,7 +189,7 @@ pub(crate) mod protocol {
     use super::{BoosterPack, MainCard, Voucher};
     use crate::{
         balatro::{
-            Screen,
+            GameError, Screen,
             blinds::protocol::BlindInfo,
             boosters::{
                 OpenArcanaPack, OpenBuffoonPack, OpenCelestialPack, OpenSpectralPack,
@@ -208,7 +223,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for ShopBuyMain {
-        type Expect = Result<ShopInfo, String>;
+        type Expect = Result<ShopInfo, GameError>;
     }
 
     impl Packet for ShopBuyMain {
@@ -223,7 +238,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for ShopBuyUse {
-        type Expect = Result<ShopInfo, String>;
+        type Expect = Result<ShopInfo, GameError>;
     }
 
     impl Packet for ShopBuyUse {
@@ -238,7 +253,7 @@ pub(crate) mod protocol {
     }
 
     impl Request for ShopBuyVoucher {
-        type Expect = Result<ShopInfo, String>;
+        type Expect = Result<ShopInfo, GameError>;
     }
 
     impl Packet for ShopBuyVoucher {
@@ -254,7 +269,7 @@ pub(crate) mod protocol {
     }
 
     impl<'a> Request for ShopBuyBooster<'a> {
-        type Expect = Result<BoughtBooster<'a>, String>;
+        type Expect = Result<BoughtBooster<'a>, GameError>;
     }
 
     impl Packet for ShopBuyBooster<'_> {
@@ -284,7 +299,7 @@ pub(crate) mod protocol {
     pub struct ShopReroll {}
 
     impl Request for ShopReroll {
-        type Expect = Result<ShopInfo, String>;
+        type Expect = Result<ShopInfo, GameError>;
     }
 
     impl Packet for ShopReroll {
@@ -297,7 +312,7 @@ pub(crate) mod protocol {
     pub struct ShopContinue {}
 
     impl Request for ShopContinue {
-        type Expect = Result<BlindInfo, String>;
+        type Expect = Result<BlindInfo, GameError>;
     }
 
     impl Packet for ShopContinue {
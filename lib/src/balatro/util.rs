@@ -1,11 +1,11 @@
 #[doc(hidden)]
 #[macro_export]
 macro_rules! balatro_enum {
-    // Handle mixed variants
-    ($name:ident {
+    // Handle mixed variants, optionally named by a `fn(&Self) -> &'static str`
+    ($name:ident $(($names:expr))? {
         $(
             $variant:ident $(
-                { $($field:ident: $field_type:ty),* $(,)? }
+                { $($field:ident: $field_type:ty $(= $init:expr)?),* $(,)? }
             )? = $identifier:literal
         ),*
         $(,)?
@@ -17,5 +17,123 @@ macro_rules! balatro_enum {
                 $variant $({ $($field: $field_type),* })?,
             )*
         }
+
+        $crate::balatro_enum!(@impl $name $(($names))?, &str, &'static str {
+            $($variant $({ $($field $(= $init)?),* })? = $identifier),*
+        });
     };
+    // Handle variants the game sends as numbers
+    ($name:ident: $repr:ident {
+        $($variant:ident = $identifier:literal),*
+        $(,)?
+    }) => {
+        #[derive(Serialize_repr, Deserialize_repr, Clone, Copy, Debug, PartialEq)]
+        #[repr($repr)]
+        pub enum $name {
+            $($variant = $identifier,)*
+        }
+
+        $crate::balatro_enum!(@impl $name, $repr, $repr {
+            $($variant = $identifier),*
+        });
+    };
+    // A field's value in `from_key` and `iter`: as given, or its default
+    (@init) => { Default::default() };
+    (@init $init:expr) => { $init };
+    // A variant's name: from the given function, or its identifier split into words
+    (@name $self:ident $idents:tt) => {
+        std::borrow::Cow::Owned($crate::balatro::util::words(match $self $idents))
+    };
+    (@name $self:ident $idents:tt ($names:expr)) => {{
+        let names: fn(&Self) -> &'static str = $names;
+        std::borrow::Cow::Borrowed(names($self))
+    }};
+    (@impl $name:ident $(($names:expr))?, $key_in:ty, $key:ty {
+        $($variant:ident $({ $($field:ident $(= $init:expr)?),* })? = $identifier:literal),*
+    }) => {
+        impl $name {
+            /// The game's key for this variant, as sent over the wire.
+            pub fn key(&self) -> $key {
+                match self {
+                    $($name::$variant { .. } => $identifier,)*
+                }
+            }
+
+            /// The variant for one of the game's keys. As the key alone does not
+            /// carry them, any fields take the value given in the definition, or
+            /// their default if it gives none.
+            pub fn from_key(key: $key_in) -> Option<Self> {
+                match key {
+                    $($identifier => Some($name::$variant $({ $($field: $crate::balatro_enum!(@init $($init)?)),* })?),)*
+                    _ => None,
+                }
+            }
+
+            /// Every variant, in declaration order, with fields as in [`Self::from_key`].
+            pub fn iter() -> impl Iterator<Item = Self> {
+                [$($name::$variant $({ $($field: $crate::balatro_enum!(@init $($init)?)),* })?),*].into_iter()
+            }
+
+            /// The name the game shows for this variant.
+            pub fn name(&self) -> std::borrow::Cow<'static, str> {
+                $crate::balatro_enum!(@name self {
+                    $($name::$variant { .. } => stringify!($variant),)*
+                } $(($names))?)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.name())
+            }
+        }
+
+        /// Parses the name shown by `Display`, ignoring case, spaces and punctuation.
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let name = $crate::balatro::util::squash(s);
+                Self::iter()
+                    .find(|variant| $crate::balatro::util::squash(&variant.name()) == name)
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid {}. Valid options are: {}",
+                            $crate::balatro::util::words(stringify!($name)).to_lowercase(),
+                            Self::iter()
+                                .map(|variant| variant.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    })
+            }
+        }
+    };
+}
+
+/// Splits a CamelCase identifier into words, e.g. `ThreeOfAKind` into `Three Of A Kind`.
+#[doc(hidden)]
+pub fn words(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut words = String::with_capacity(ident.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        let starts_word = i > 0
+            && c.is_uppercase()
+            && (!chars[i - 1].is_uppercase()
+                || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+        if starts_word {
+            words.push(' ');
+        }
+        words.push(c);
+    }
+    words
+}
+
+/// Lowercases `s` and drops everything but letters and digits, for loose name matching.
+#[doc(hidden)]
+pub fn squash(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
 }
@@ -1,23 +1,297 @@
 #![doc = include_str!("../README.md")]
 pub mod balatro;
-mod net;
+pub mod net;
+pub mod scoring;
+pub mod server;
+mod session;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 use balatro::Balatro;
-use net::Socket;
+use net::{ConnectionConfig, Secret, Socket, Transport};
+pub use server::Server;
+pub use session::Session;
+
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+#[cfg(unix)]
+use std::path::Path;
+use std::time::Duration;
+use tokio::net::{TcpStream, ToSocketAddrs};
 
 pub struct Remotro {
     socket: Socket,
+    secret: Option<Secret>,
 }
 
 impl Remotro {
+    /// Hosts a socket on the given address with the default [`ConnectionConfig`].
     pub async fn host(host: impl AsRef<str>, port: u16) -> Result<Self, net::Error> {
-        Ok(Self {
-            socket: Socket::bind(host, port).await?,
-        })
+        Self::builder().host(host, port).await
+    }
+
+    /// Hosts a Unix domain socket at `path` with the default [`ConnectionConfig`].
+    ///
+    /// Unlike a TCP port, the socket file can be protected with file permissions
+    /// and does not collide between sandboxed game instances. It is removed when
+    /// the [`Remotro`] is dropped.
+    #[cfg(unix)]
+    pub async fn host_unix(path: impl AsRef<Path>) -> Result<Self, net::Error> {
+        Self::builder().host_unix(path).await
+    }
+
+    /// Hosts a WebSocket listener on the given address with the default [`ConnectionConfig`].
+    ///
+    /// Packets travel as `kind!body` text frames, so browser front-ends and
+    /// reverse proxies can sit between the game and the controller.
+    #[cfg(feature = "websocket")]
+    pub async fn host_websocket(host: impl AsRef<str>, port: u16) -> Result<Self, net::Error> {
+        Self::builder().host_websocket(host, port).await
+    }
+
+    /// Connects to a game that listens on `addr`, instead of waiting for the
+    /// game to connect, using the default [`ConnectionConfig`].
+    ///
+    /// Useful when the game is behind NAT and the controller is not. Apart from
+    /// who dials whom, the connection behaves exactly like an accepted one.
+    pub async fn connect(addr: impl ToSocketAddrs) -> Result<Balatro, net::Error> {
+        Self::builder().connect(addr).await
+    }
+
+    /// Starts configuring a [`Remotro`] before hosting it.
+    pub fn builder() -> RemotroBuilder {
+        RemotroBuilder::default()
     }
 
+    /// Waits for a game to connect and agrees on a protocol version with it.
+    /// If a [`Secret`] was configured, the game must also pass the
+    /// authentication handshake, otherwise this fails with [`net::Error::Unauthorized`].
     pub async fn accept(&mut self) -> Result<Balatro, net::Error> {
-        let connection = self.socket.accept().await?;
-        Ok(Balatro::new(connection))
+        let connection = self.socket.accept().await?.upgrade().await?;
+        establish(connection, self.secret.as_ref(), false).await
+    }
+
+    /// Keeps accepting games in the background, for driving many at once.
+    /// See [`Server`].
+    pub fn serve(self) -> Server {
+        Server::new(self.socket, self.secret)
+    }
+
+    /// The settings accepted connections are created with.
+    pub fn config(&self) -> &ConnectionConfig {
+        self.socket.config()
+    }
+
+    /// The TCP address games connect to, e.g. to learn the port picked when
+    /// hosting on port 0. `None` for a Unix domain socket.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.socket.local_addr()
+    }
+}
+
+/// Runs authentication, if a secret is set, and then the hello exchange on a
+/// freshly accepted connection, so only authenticated games learn our version
+/// and capabilities. Through a relay, the first step waits for the game to join.
+async fn establish(
+    mut connection: net::Connection,
+    secret: Option<&Secret>,
+    relayed: bool,
+) -> Result<Balatro, net::Error> {
+    if let Some(secret) = secret {
+        net::auth::authenticate(&mut connection, secret, relayed).await?;
+    }
+    if relayed {
+        connection.handshake_relayed().await?;
+    } else {
+        connection.handshake().await?;
+    }
+    Ok(Balatro::new(connection))
+}
+
+/// Builder for a [`Remotro`] with a non-default [`ConnectionConfig`].
+///
+/// ```no_run
+/// # async fn run() -> Result<(), remotro::net::Error> {
+/// use std::time::Duration;
+///
+/// let remotro = remotro::Remotro::builder()
+///     .inactivity_timeout(Duration::from_secs(20))
+///     .max_ping_retries(5)
+///     .request_timeout(Duration::from_secs(30))
+///     .host("127.0.0.1", 34143)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RemotroBuilder {
+    config: ConnectionConfig,
+    secret: Option<Secret>,
+    room: Option<String>,
+    #[cfg(feature = "tls")]
+    tls: Option<net::TlsConfig>,
+}
+
+impl RemotroBuilder {
+    /// Replaces the whole connection config.
+    pub fn config(mut self, config: ConnectionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// How long a connection may be idle before a ping is sent.
+    pub fn inactivity_timeout(mut self, timeout: Duration) -> Self {
+        self.config.inactivity_timeout = timeout;
+        self
+    }
+
+    /// How long to wait for any packet after a ping before pinging again.
+    pub fn ping_response_timeout(mut self, timeout: Duration) -> Self {
+        self.config.ping_response_timeout = timeout;
+        self
+    }
+
+    /// How many unanswered pings are tolerated before the connection times out.
+    pub fn max_ping_retries(mut self, retries: u8) -> Self {
+        self.config.max_ping_retries = retries;
+        self
+    }
+
+    /// Size of the channels between a connection and its background task.
+    pub fn channel_buffer_size(mut self, size: NonZeroUsize) -> Self {
+        self.config.channel_buffer_size = size;
+        self
+    }
+
+    /// Deadline for the game to answer a single request.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Deadline for a game to connect in [`Remotro::accept`].
+    pub fn accept_timeout(mut self, timeout: Duration) -> Self {
+        self.config.accept_timeout = Some(timeout);
+        self
+    }
+
+    /// Deadline for reaching the game in [`RemotroBuilder::connect`].
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Deadline for the game to answer each step of setting up a connection.
+    pub fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.config.handshake_timeout = timeout;
+        self
+    }
+
+    /// Deadline for the game to answer the hello exchange, after which it is
+    /// taken to predate the exchange. Games that predate it wait this long on
+    /// every accept; see [`ConnectionConfig::hello_timeout`].
+    pub fn hello_timeout(mut self, timeout: Duration) -> Self {
+        self.config.hello_timeout = timeout;
+        self
+    }
+
+    /// How long [`Session::resume`] waits for the same game to reconnect.
+    pub fn session_grace(mut self, grace: Duration) -> Self {
+        self.config.session_grace = grace;
+        self
+    }
+
+    /// Records every packet of every connection to a JSON Lines transcript.
+    /// See [`net::transcript`].
+    pub fn record(mut self, recorder: net::transcript::Recorder) -> Self {
+        self.config.recorder = Some(recorder);
+        self
+    }
+
+    /// Requires games to prove knowledge of `secret` with an HMAC
+    /// challenge/response before they are accepted.
+    pub fn secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(Secret::new(secret));
+        self
+    }
+
+    /// Joins `room` when connecting, for reaching a game through a relay that
+    /// pairs controllers and games by room code. Only used by [`RemotroBuilder::connect`].
+    pub fn room(mut self, room: impl Into<String>) -> Self {
+        self.room = Some(room.into());
+        self
+    }
+
+    /// Wraps every accepted connection in TLS. Requires the `tls` feature.
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, tls: net::TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Hosts a TCP socket on the given address.
+    pub async fn host(self, host: impl AsRef<str>, port: u16) -> Result<Remotro, net::Error> {
+        let socket = Socket::bind(host, port, self.config.clone()).await?;
+        Ok(self.finish(socket))
+    }
+
+    /// Hosts a WebSocket listener on the given address.
+    #[cfg(feature = "websocket")]
+    pub async fn host_websocket(
+        self,
+        host: impl AsRef<str>,
+        port: u16,
+    ) -> Result<Remotro, net::Error> {
+        let socket = Socket::bind_websocket(host, port, self.config.clone()).await?;
+        Ok(self.finish(socket))
+    }
+
+    /// Hosts a Unix domain socket at `path`.
+    #[cfg(unix)]
+    pub async fn host_unix(self, path: impl AsRef<Path>) -> Result<Remotro, net::Error> {
+        let socket = Socket::bind_unix(path, self.config.clone())?;
+        Ok(self.finish(socket))
+    }
+
+    /// Connects to a game that listens on `addr`. The handshake, heartbeat and
+    /// [`secret`](Self::secret) work as for accepted games.
+    ///
+    /// TLS is only supported when hosting, so this fails if TLS was configured.
+    pub async fn connect(self, addr: impl ToSocketAddrs) -> Result<Balatro, net::Error> {
+        #[cfg(feature = "tls")]
+        if self.tls.is_some() {
+            return Err(net::Error::Message(std::borrow::Cow::Borrowed(
+                "TLS is only supported when hosting",
+            )));
+        }
+        let stream = match self.config.connect_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, TcpStream::connect(addr))
+                .await
+                .map_err(|_| net::Error::Timeout)??,
+            None => TcpStream::connect(addr).await?,
+        };
+        let mut transport = net::StreamTransport::new(stream, &self.config);
+        let relayed = self.room.is_some();
+        if let Some(room) = self.room {
+            let join = net::relay::Join {
+                room,
+                role: net::relay::Role::Controller,
+            };
+            transport.send(join.encode()?).await?;
+        }
+        let connection = net::Connection::new(transport, &self.config);
+        establish(connection, self.secret.as_ref(), relayed).await
+    }
+
+    fn finish(self, socket: Socket) -> Remotro {
+        #[cfg(feature = "tls")]
+        let socket = match self.tls {
+            Some(tls) => socket.with_tls(tls),
+            None => socket,
+        };
+        Remotro {
+            socket,
+            secret: self.secret,
+        }
     }
 }
@@ -0,0 +1,120 @@
+use super::{Connection, Error};
+
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use sha2::Sha256;
+use std::borrow::Cow;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Number of random bytes in a challenge nonce.
+const NONCE_LEN: usize = 32;
+
+/// A pre-shared key that games must prove knowledge of before they are
+/// handed out by [`Remotro::accept`](crate::Remotro::accept).
+///
+/// The key itself never goes over the wire. The game answers a random
+/// challenge with `HMAC-SHA256(key, nonce)`.
+#[derive(Clone)]
+pub struct Secret(Vec<u8>);
+
+impl Secret {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self(key.into())
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.0).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+/// Challenges the peer on `connection` and checks its answer against `secret`.
+/// Fails with [`Error::Unauthorized`] if the peer cannot answer correctly, or
+/// does not answer within [`ConnectionConfig::handshake_timeout`](super::ConnectionConfig::handshake_timeout).
+///
+/// Through a relay, the game may not have joined yet, so the challenge waits as
+/// long as any request instead.
+pub(crate) async fn authenticate(
+    connection: &mut Connection,
+    secret: &Secret,
+    relayed: bool,
+) -> Result<(), Error> {
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::fill(&mut nonce)
+        .map_err(|e| Error::Message(Cow::Owned(format!("Failed to generate auth nonce: {e}"))))?;
+
+    let challenge = protocol::AuthChallenge {
+        nonce: hex::encode(nonce),
+    };
+    let response = if relayed {
+        connection.request(challenge).await
+    } else {
+        connection.setup(challenge).await
+    };
+    let response = match response {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            warn!("Peer refused auth challenge: {e}");
+            return Err(Error::Unauthorized);
+        }
+        Err(Error::Timeout) if !relayed => {
+            warn!("Peer did not answer auth challenge");
+            return Err(Error::Unauthorized);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let Ok(tag) = hex::decode(&response.mac) else {
+        warn!("Peer sent a malformed auth response");
+        return Err(Error::Unauthorized);
+    };
+    let mut mac = secret.mac();
+    mac.update(&nonce);
+    if mac.verify_slice(&tag).is_err() {
+        warn!("Peer failed auth challenge");
+        return Err(Error::Unauthorized);
+    }
+    info!("Peer authenticated");
+    Ok(())
+}
+
+pub(crate) mod protocol {
+    use crate::net::protocol::{Packet, Request, Response};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize)]
+    pub struct AuthChallenge {
+        /// Hex encoded random bytes to be signed by the game.
+        pub nonce: String,
+    }
+
+    impl Request for AuthChallenge {
+        type Expect = Result<AuthResponse, String>;
+    }
+
+    impl Packet for AuthChallenge {
+        fn kind() -> String {
+            "auth/challenge".to_string()
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct AuthResponse {
+        /// Hex encoded `HMAC-SHA256(secret, nonce)`, computed over the raw nonce bytes.
+        pub mac: String,
+    }
+
+    impl Response for AuthResponse {}
+
+    impl Packet for AuthResponse {
+        fn kind() -> String {
+            "auth/response".to_string()
+        }
+    }
+}
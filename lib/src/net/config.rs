@@ -0,0 +1,77 @@
+use super::transcript::Recorder;
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+/// Duration of inactivity (no packets received or sent) before a ping is sent.
+const DEFAULT_INACTIVITY_TIMEOUT: Duration = Duration::from_secs(7);
+/// Duration to wait for a response (any packet) after sending a ping before retrying.
+const DEFAULT_PING_RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Maximum number of ping retries before closing the connection due to timeout.
+const DEFAULT_MAX_PING_RETRIES: u8 = 3;
+/// Size of the MPSC channels used for communication between the main struct and the background task.
+const DEFAULT_CHANNEL_BUFFER_SIZE: NonZeroUsize = NonZeroUsize::new(32).unwrap();
+/// Deadline for each step of setting up a connection, such as authentication.
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Deadline for the game to answer the hello exchange before it is taken to predate it.
+const DEFAULT_HELLO_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long a [`Session`](crate::Session) waits for its game to reconnect.
+const DEFAULT_SESSION_GRACE: Duration = Duration::from_secs(30);
+
+/// Settings for the heartbeat, buffering and deadlines of every connection
+/// accepted by a [`Remotro`](crate::Remotro).
+///
+/// The defaults match the values the mod is tuned for. Slow machines where the
+/// game stalls during animations may want a longer inactivity timeout or more
+/// ping retries.
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    /// Duration of inactivity (no packets received or sent) before a ping is sent.
+    pub inactivity_timeout: Duration,
+    /// Duration to wait for any packet after sending a ping before retrying.
+    pub ping_response_timeout: Duration,
+    /// Maximum number of unanswered pings before the connection times out.
+    pub max_ping_retries: u8,
+    /// Size of the channels between a connection and its background task.
+    pub channel_buffer_size: NonZeroUsize,
+    /// Deadline for the game to answer a single request. `None` waits forever.
+    pub request_timeout: Option<Duration>,
+    /// Deadline for a game to connect when accepting. `None` waits forever.
+    pub accept_timeout: Option<Duration>,
+    /// Deadline for reaching a game when connecting to it. `None` leaves it to
+    /// the operating system.
+    pub connect_timeout: Option<Duration>,
+    /// Deadline for the game to answer each step of setting up a connection,
+    /// such as the authentication challenge. Unlike `request_timeout`, this is
+    /// always bounded, so a silent peer cannot stall [`Remotro::accept`](crate::Remotro::accept).
+    pub handshake_timeout: Duration,
+    /// Deadline for the game to answer the hello exchange. A game that does
+    /// not answer in time is taken to predate the exchange. Nothing is
+    /// remembered between connections, so such a game adds this delay to
+    /// every accept, including each reconnect taken by a [`Server`](crate::Server)
+    /// or [`Session`](crate::Session). Kept short for that reason.
+    pub hello_timeout: Duration,
+    /// How long [`Session::resume`](crate::Session::resume) waits for the same
+    /// game to reconnect before giving up.
+    pub session_grace: Duration,
+    /// Where to record every packet sent and received. `None` records nothing.
+    pub recorder: Option<Recorder>,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            inactivity_timeout: DEFAULT_INACTIVITY_TIMEOUT,
+            ping_response_timeout: DEFAULT_PING_RESPONSE_TIMEOUT,
+            max_ping_retries: DEFAULT_MAX_PING_RETRIES,
+            channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE,
+            request_timeout: None,
+            accept_timeout: None,
+            connect_timeout: None,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            hello_timeout: DEFAULT_HELLO_TIMEOUT,
+            session_grace: DEFAULT_SESSION_GRACE,
+            recorder: None,
+        }
+    }
+}
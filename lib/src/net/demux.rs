@@ -0,0 +1,150 @@
+use super::transcript::{Direction, Recorder};
+use super::transport::Transport;
+use super::{Error, Event, protocol};
+
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+use tokio::time::Instant;
+
+type Reply = oneshot::Sender<Result<String, Error>>;
+
+/// A request waiting to be written, and where to deliver its response.
+pub(crate) struct Pending {
+    pub packet: String,
+    /// Id the game will echo in its response, if the game supports request ids.
+    pub id: Option<u64>,
+    /// Kind of the response that answers this request.
+    pub kind: String,
+    pub reply: Reply,
+}
+
+/// A request that was written and is waiting for its response.
+struct Outstanding {
+    kind: String,
+    reply: Reply,
+    sent_at: Instant,
+}
+
+/// Owns the transport, writing requests and sorting incoming packets.
+///
+/// A response carrying a request id answers the request with that id, and is
+/// logged and dropped if that request was abandoned, e.g. after a timeout.
+/// Without an id, a packet whose kind matches the outstanding request answers
+/// it. Anything else was sent by the game on its own, e.g. because a human
+/// clicked in the game window, and is broadcast as an [`Event`] instead of
+/// desyncing the next request.
+///
+/// Relies on [`Transport::recv`] being cancel safe, since a pending receive is
+/// dropped whenever a request needs to be written.
+pub(crate) async fn run_demux(
+    mut transport: Box<dyn Transport>,
+    mut requests: mpsc::Receiver<Pending>,
+    events: broadcast::Sender<Event>,
+    // Dropped on exit, which is how the connection learns it has closed.
+    _alive: watch::Sender<()>,
+    recorder: Option<(Recorder, u64)>,
+) {
+    info!("Demultiplexer started.");
+    let record = |direction, packet: &str, id, latency| {
+        if let Some((recorder, connection)) = &recorder {
+            recorder.record(*connection, direction, packet, id, latency);
+        }
+    };
+    let mut by_id: HashMap<u64, Outstanding> = HashMap::new();
+    let mut waiting: Option<Outstanding> = None;
+    loop {
+        tokio::select! {
+            request = requests.recv() => {
+                // The connection was dropped.
+                let Some(request) = request else { break };
+                if recorder.is_some() {
+                    let (_, packet) = protocol::untag(request.packet.clone());
+                    record(Direction::Sent, &packet, request.id, None);
+                }
+                if let Err(e) = transport.send(request.packet).await {
+                    let _ = request.reply.send(Err(e));
+                    break;
+                }
+                let outstanding = Outstanding {
+                    kind: request.kind,
+                    reply: request.reply,
+                    sent_at: Instant::now(),
+                };
+                match request.id {
+                    Some(id) => {
+                        // Forget requests whose caller gave up and never got an answer.
+                        by_id.retain(|_, outstanding| !outstanding.reply.is_closed());
+                        by_id.insert(id, outstanding);
+                    }
+                    None => {
+                        if let Some(abandoned) = waiting.replace(outstanding) {
+                            debug!("Abandoned request for {} was replaced.", abandoned.kind);
+                        }
+                    }
+                }
+            }
+            packet = transport.recv() => {
+                let packet = match packet {
+                    Ok(packet) => packet,
+                    Err(e) => {
+                        // A connection sends one request at a time, so at most one
+                        // caller is still waiting, with or without an id. It gets
+                        // the transport's error; abandoned requests are dropped.
+                        let live = waiting
+                            .take()
+                            .into_iter()
+                            .chain(by_id.drain().map(|(_, outstanding)| outstanding))
+                            .find(|outstanding| !outstanding.reply.is_closed());
+                        if let Some(outstanding) = live {
+                            let _ = outstanding.reply.send(Err(e));
+                        }
+                        break;
+                    }
+                };
+                let (id, packet) = protocol::untag(packet);
+                let (kind, body) = packet.split_once('!').unwrap_or((&packet, ""));
+                if let Some(id) = id {
+                    let outstanding = by_id.remove(&id);
+                    record(
+                        Direction::Received,
+                        &packet,
+                        Some(id),
+                        outstanding.as_ref().map(|o| o.sent_at.elapsed()),
+                    );
+                    match outstanding {
+                        Some(outstanding) if !outstanding.reply.is_closed() => {
+                            let _ = outstanding.reply.send(Ok(packet));
+                        }
+                        _ => warn!("Dropping orphaned {kind} response to request {id}."),
+                    }
+                    continue;
+                }
+                match waiting.take() {
+                    Some(outstanding) if outstanding.kind == kind => {
+                        record(
+                            Direction::Received,
+                            &packet,
+                            None,
+                            Some(outstanding.sent_at.elapsed()),
+                        );
+                        if outstanding.reply.send(Ok(packet)).is_err() {
+                            warn!("Dropping {} response to an abandoned request.", outstanding.kind);
+                        }
+                    }
+                    other => {
+                        waiting = other;
+                        record(Direction::Received, &packet, None, None);
+                        debug!("Received unsolicited {kind} packet.");
+                        // Nobody listening is fine, events are best effort.
+                        let _ = events.send(Event {
+                            kind: kind.to_string(),
+                            body: body.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    info!("Demultiplexer finished.");
+}
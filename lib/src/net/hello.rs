@@ -0,0 +1,167 @@
+use super::{Connection, Error};
+
+use log::{info, warn};
+use semver::{Version, VersionReq};
+use std::borrow::Cow;
+
+/// Version of the protocol spoken by this crate, advertised in the hello exchange.
+pub const PROTOCOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The game echoes request ids, framing packets as `kind#id!body`.
+pub(crate) const REQUEST_IDS: &str = "request-ids";
+
+/// The game reports a state epoch with every response, and refuses actions
+/// that echo an older one.
+pub(crate) const STATE_EPOCHS: &str = "state-epochs";
+
+/// Optional protocol features this crate understands, advertised in the hello exchange.
+const CAPABILITIES: &[&str] = &[REQUEST_IDS, STATE_EPOCHS];
+
+/// What the game reported about itself when the connection was set up.
+#[derive(Debug, Clone)]
+pub struct Peer {
+    /// The game's protocol version. Games that predate the hello exchange are
+    /// assumed to speak ours, and the version in each response is checked instead.
+    pub version: Version,
+    pub capabilities: Vec<String>,
+    /// Identifies the running game instance, and stays the same when it reconnects.
+    pub session: Option<String>,
+}
+
+impl Peer {
+    /// Whether the game advertised the given optional protocol feature.
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+}
+
+/// Whether two protocol versions can talk to each other under semver rules:
+/// the same major version, or for `0.x` versions the same minor version.
+pub(crate) fn compatible(ours: &Version, theirs: &Version) -> bool {
+    match (ours.major, theirs.major) {
+        (0, 0) if ours.minor == 0 && theirs.minor == 0 => ours.patch == theirs.patch,
+        (0, 0) => ours.minor == theirs.minor,
+        (a, b) => a == b,
+    }
+}
+
+pub(crate) fn ours() -> Version {
+    Version::parse(PROTOCOL_VERSION).expect("crate version is valid semver")
+}
+
+pub(crate) fn mismatch(theirs: impl Into<String>) -> Error {
+    Error::VersionMismatch {
+        ours: PROTOCOL_VERSION.to_string(),
+        theirs: theirs.into(),
+    }
+}
+
+/// Exchanges versions and capabilities with the game. Fails with
+/// [`Error::VersionMismatch`] unless each side accepts the other's version.
+///
+/// With `legacy_fallback`, a game that does not answer within the hello
+/// timeout is taken to predate the exchange, and is treated as speaking our
+/// version without any optional features. The fallback is not cached, so such
+/// a game costs a full hello timeout on every connection. Without it, the game
+/// is given as long as any other request.
+pub(crate) async fn negotiate(
+    connection: &mut Connection,
+    legacy_fallback: bool,
+) -> Result<Peer, Error> {
+    let ours = ours();
+    let hello = protocol::Hello {
+        version: PROTOCOL_VERSION.to_string(),
+        accepts: VersionReq::parse(&format!("^{}.{}", ours.major, ours.minor))
+            .expect("caret requirement is valid")
+            .to_string(),
+        capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+    };
+    let reply = if legacy_fallback {
+        connection.probe(hello).await
+    } else {
+        connection.request(hello).await
+    };
+    let reply = match reply {
+        Err(Error::Timeout) if legacy_fallback => {
+            warn!("Game did not answer hello, assuming it predates the exchange");
+            return Ok(Peer {
+                version: ours,
+                capabilities: Vec::new(),
+                session: None,
+            });
+        }
+        reply => {
+            reply?.map_err(|e| Error::Message(Cow::Owned(format!("Game rejected hello: {e}"))))?
+        }
+    };
+
+    let theirs = Version::parse(&reply.version).map_err(|_| mismatch(&reply.version))?;
+    if !compatible(&ours, &theirs) {
+        warn!("Game speaks protocol {theirs}, we speak {ours}");
+        return Err(mismatch(&reply.version));
+    }
+    if let Some(accepts) = &reply.accepts {
+        let accepts = VersionReq::parse(accepts).map_err(|e| {
+            Error::Message(Cow::Owned(format!("Game sent invalid version range: {e}")))
+        })?;
+        if !accepts.matches(&ours) {
+            warn!("Game only accepts protocol {accepts}, we speak {ours}");
+            return Err(mismatch(&reply.version));
+        }
+    }
+
+    info!(
+        "Negotiated protocol {ours} with game on {theirs}, capabilities {:?}",
+        reply.capabilities
+    );
+    Ok(Peer {
+        version: theirs,
+        capabilities: reply.capabilities,
+        session: reply.session,
+    })
+}
+
+pub(crate) mod protocol {
+    use crate::net::protocol::{Packet, Request, Response};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize)]
+    pub struct Hello {
+        pub version: String,
+        /// Range of game protocol versions this side accepts.
+        pub accepts: String,
+        pub capabilities: Vec<String>,
+    }
+
+    impl Request for Hello {
+        type Expect = Result<HelloReply, String>;
+    }
+
+    impl Packet for Hello {
+        fn kind() -> String {
+            "hello".to_string()
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct HelloReply {
+        pub version: String,
+        /// Range of client protocol versions the game accepts, if it restricts them
+        /// further than semver compatibility.
+        #[serde(default)]
+        pub accepts: Option<String>,
+        #[serde(default)]
+        pub capabilities: Vec<String>,
+        /// Token identifying the game instance, for resuming sessions.
+        #[serde(default)]
+        pub session: Option<String>,
+    }
+
+    impl Response for HelloReply {}
+
+    impl Packet for HelloReply {
+        fn kind() -> String {
+            "hello".to_string()
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use super::Error;
+use super::transport::{BoxFuture, Transport};
+
+use std::borrow::Cow;
+use tokio::sync::mpsc;
+
+/// One end of an in-process [`Transport`] created by [`duplex`].
+///
+/// There is no heartbeat, since both ends live in the same process. Dropping
+/// one end closes the other.
+pub struct MemoryTransport {
+    tx: mpsc::Sender<String>,
+    rx: mpsc::Receiver<String>,
+}
+
+/// Creates a pair of connected [`MemoryTransport`]s. Packets sent on one end
+/// are received on the other, with up to `buffer` packets in flight each way.
+pub fn duplex(buffer: usize) -> (MemoryTransport, MemoryTransport) {
+    let (tx_a, rx_a) = mpsc::channel(buffer);
+    let (tx_b, rx_b) = mpsc::channel(buffer);
+    (
+        MemoryTransport { tx: tx_a, rx: rx_b },
+        MemoryTransport { tx: tx_b, rx: rx_a },
+    )
+}
+
+impl Transport for MemoryTransport {
+    fn send(&mut self, packet: String) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.tx
+                .send(packet)
+                .await
+                .map_err(|_| Error::Channel(Cow::Borrowed("Other end of transport was dropped")))
+        })
+    }
+
+    fn recv(&mut self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async move { self.rx.recv().await.ok_or(Error::ConnectionClosed) })
+    }
+}
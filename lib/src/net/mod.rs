@@ -1,44 +1,266 @@
+pub(crate) mod auth;
+mod config;
+mod demux;
+pub(crate) mod hello;
+mod memory;
 pub mod protocol;
-mod tcp;
+pub mod relay;
+mod socket;
+mod stream;
+#[cfg(feature = "tls")]
+mod tls;
+pub mod transcript;
+mod transport;
+#[cfg(feature = "websocket")]
+mod ws;
 
-use protocol::Request;
-use tcp::TcpStreamExt;
-use tokio::net::TcpListener;
+pub use auth::Secret;
+pub use config::ConnectionConfig;
+pub use hello::{PROTOCOL_VERSION, Peer};
+pub use memory::{MemoryTransport, duplex};
+pub(crate) use socket::Socket;
+pub use stream::StreamTransport;
+#[cfg(feature = "tls")]
+pub use tls::TlsConfig;
+pub use transport::{BoxFuture, Transport};
+#[cfg(feature = "websocket")]
+pub use ws::WebSocketTransport;
+
+use protocol::{Packet, Request};
 
 use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
 
-pub struct Socket {
-    listener: TcpListener,
+/// A request/response channel to a single game, over any [`Transport`].
+///
+/// The transport is driven by a background task, which hands responses back
+/// to the requests that asked for them and everything else to [`Connection::events`].
+pub struct Connection {
+    requests: mpsc::Sender<demux::Pending>,
+    events: broadcast::Sender<Event>,
+    /// Closed by the background task when it exits.
+    alive: watch::Receiver<()>,
+    request_timeout: Option<Duration>,
+    handshake_timeout: Duration,
+    hello_timeout: Duration,
+    peer: Option<Peer>,
+    /// Id for the next request, once the game has agreed to echo ids.
+    next_id: Option<u64>,
+    /// State epoch of the game's latest response, if it reports them.
+    epoch: Option<u64>,
 }
 
-impl Socket {
-    pub async fn bind(host: impl AsRef<str>, port: u16) -> Result<Self, Error> {
-        let listener = TcpListener::bind(format!("{}:{}", host.as_ref(), port)).await?;
-        Ok(Self { listener })
+/// Watches a [`Connection`] from elsewhere, e.g. while another task holds it.
+#[derive(Clone)]
+pub(crate) struct Observer {
+    events: broadcast::Sender<Event>,
+    alive: watch::Receiver<()>,
+}
+
+impl Observer {
+    pub(crate) fn is_closed(&self) -> bool {
+        self.alive.has_changed().is_err()
+    }
+
+    pub(crate) fn closed(&self) -> impl Future<Output = ()> + Send + use<> {
+        let mut alive = self.alive.clone();
+        async move { while alive.changed().await.is_ok() {} }
     }
 
-    pub async fn accept(&mut self) -> Result<Connection, Error> {
-        let (stream, _) = self.listener.accept().await?;
-        Ok(Connection::new(TcpStreamExt::new(stream)))
+    pub(crate) fn events(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
     }
 }
 
-pub struct Connection {
-    stream: TcpStreamExt,
+/// A packet the game sent without being asked, in raw `kind!body` form.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub kind: String,
+    pub body: String,
 }
 
 impl Connection {
-    fn new(stream: TcpStreamExt) -> Self {
-        Self { stream }
+    /// Wraps a transport. Only the request and channel settings of `config` are
+    /// used here, heartbeat settings belong to the transport itself.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn new(transport: impl Transport + 'static, config: &ConnectionConfig) -> Self {
+        let (requests, rx_requests) = mpsc::channel(config.channel_buffer_size.get());
+        let (events, _) = broadcast::channel(config.channel_buffer_size.get());
+        let (tx_alive, alive) = watch::channel(());
+        let recorder = config.recorder.clone().map(|recorder| {
+            static CONNECTIONS: AtomicU64 = AtomicU64::new(0);
+            (recorder, CONNECTIONS.fetch_add(1, Ordering::Relaxed))
+        });
+        tokio::spawn(demux::run_demux(
+            Box::new(transport),
+            rx_requests,
+            events.clone(),
+            tx_alive,
+            recorder,
+        ));
+        Self {
+            requests,
+            events,
+            alive,
+            request_timeout: config.request_timeout,
+            handshake_timeout: config.handshake_timeout,
+            hello_timeout: config.hello_timeout,
+            peer: None,
+            next_id: None,
+            epoch: None,
+        }
+    }
+
+    /// Runs the one-time hello exchange, agreeing on a protocol version and
+    /// learning the game's capabilities. [`Remotro::accept`](crate::Remotro::accept)
+    /// does this for you; call it yourself when using a custom [`Transport`].
+    ///
+    /// If the game supports request ids, every later request carries one, so a
+    /// late response to an abandoned request can never answer the next one.
+    ///
+    /// A game that does not answer within [`ConnectionConfig::hello_timeout`]
+    /// is taken to predate the exchange, and gets no optional protocol features.
+    pub async fn handshake(&mut self) -> Result<&Peer, Error> {
+        let peer = hello::negotiate(self, true).await?;
+        Ok(self.agree(peer))
+    }
+
+    /// Runs the hello exchange through a relay, where the game may not have
+    /// joined yet. This waits as long as any request, and never falls back to
+    /// games that predate the exchange, as those cannot use relays.
+    pub(crate) async fn handshake_relayed(&mut self) -> Result<&Peer, Error> {
+        let peer = hello::negotiate(self, false).await?;
+        Ok(self.agree(peer))
+    }
+
+    fn agree(&mut self, peer: Peer) -> &Peer {
+        if peer.supports(hello::REQUEST_IDS) {
+            self.next_id.get_or_insert(0);
+        }
+        self.peer.insert(peer)
+    }
+
+    /// The game on the other end, once [`Connection::handshake`] has succeeded.
+    pub fn peer(&self) -> Option<&Peer> {
+        self.peer.as_ref()
+    }
+
+    /// Sends a request and waits for its response, bounded by
+    /// [`ConnectionConfig::request_timeout`].
+    ///
+    /// A response that arrives after its request timed out is dropped when the
+    /// game echoes request ids. Games without them are matched by kind alone,
+    /// so a late response answers the next request expecting the same kind.
+    pub(crate) async fn request<R: Request>(&mut self, req: R) -> Result<R::Expect, Error> {
+        self.send(req, None).await
+    }
+
+    /// Sends a step of setting up the connection, bounded by
+    /// [`ConnectionConfig::handshake_timeout`] rather than the request timeout.
+    pub(crate) async fn setup<R: Request>(&mut self, req: R) -> Result<R::Expect, Error> {
+        tokio::time::timeout(self.handshake_timeout, self.exchange(req, None))
+            .await
+            .map_err(|_| Error::Timeout)?
     }
 
-    pub async fn request<R: Request>(&mut self, req: R) -> Result<R::Expect, Error> {
-        self.stream.send(req).await?;
-        self.stream.recv().await
+    /// Sends the hello, bounded by [`ConnectionConfig::hello_timeout`], as
+    /// games that predate the exchange never answer it.
+    pub(crate) async fn probe<R: Request>(&mut self, req: R) -> Result<R::Expect, Error> {
+        tokio::time::timeout(self.hello_timeout, self.exchange(req, None))
+            .await
+            .map_err(|_| Error::Timeout)?
+    }
+
+    /// Sends an action taken from a screen showing the game state at `epoch`,
+    /// so the game can refuse it if its state has moved on since.
+    pub(crate) async fn act<R: Request>(
+        &mut self,
+        req: R,
+        epoch: Option<u64>,
+    ) -> Result<R::Expect, Error> {
+        self.send(req, epoch).await
+    }
+
+    /// The state epoch of the game's latest response, if it reports them.
+    pub(crate) fn epoch(&self) -> Option<u64> {
+        self.epoch
+    }
+
+    async fn send<R: Request>(&mut self, req: R, epoch: Option<u64>) -> Result<R::Expect, Error> {
+        match self.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.exchange(req, epoch))
+                .await
+                .map_err(|_| Error::Timeout)?,
+            None => self.exchange(req, epoch).await,
+        }
+    }
+
+    /// Whether the connection to the game has ended.
+    pub fn is_closed(&self) -> bool {
+        self.requests.is_closed()
+    }
+
+    /// Completes once the connection to the game has ended. The future does
+    /// not borrow the connection, so it can be awaited from another task.
+    pub fn closed(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.observer().closed()
+    }
+
+    /// Subscribes to packets the game sends on its own. Only packets received
+    /// after subscribing are seen, and a subscriber that falls more than
+    /// [`ConnectionConfig::channel_buffer_size`] packets behind misses the oldest.
+    pub fn events(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    /// A handle for watching the connection without access to it.
+    pub(crate) fn observer(&self) -> Observer {
+        Observer {
+            events: self.events.clone(),
+            alive: self.alive.clone(),
+        }
+    }
+
+    async fn exchange<R: Request>(
+        &mut self,
+        req: R,
+        epoch: Option<u64>,
+    ) -> Result<R::Expect, Error> {
+        let (reply, response) = oneshot::channel();
+        let mut packet = match epoch {
+            Some(epoch) => protocol::encode_at(&req, epoch)?,
+            None => protocol::encode(&req)?,
+        };
+        let id = self.next_id.as_mut().map(|next| {
+            *next = next.wrapping_add(1);
+            *next
+        });
+        if let Some(id) = id {
+            packet = protocol::tag(&packet, id);
+        }
+        self.requests
+            .send(demux::Pending {
+                packet,
+                id,
+                kind: R::Expect::kind(),
+                reply,
+            })
+            .await
+            .map_err(|_| Error::ConnectionClosed)?;
+        let packet = response.await.map_err(|_| Error::ConnectionClosed)??;
+        let (response, epoch) = protocol::decode_at(&packet)?;
+        if epoch.is_some() {
+            self.epoch = epoch;
+        }
+        Ok(response)
     }
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     IO(std::io::Error),
     Message(Cow<'static, str>),
@@ -46,7 +268,17 @@ pub enum Error {
     Timeout,
     ConnectionClosed,
     Channel(Cow<'static, str>),
-    VersionMismatch,
+    /// The game speaks a protocol version we cannot talk to, or the other way around.
+    VersionMismatch {
+        ours: String,
+        theirs: String,
+    },
+    /// The peer failed the pre-shared key handshake.
+    Unauthorized,
+    #[cfg(feature = "websocket")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    #[cfg(feature = "tls")]
+    Tls(tokio_rustls::rustls::Error),
 }
 
 impl std::fmt::Display for Error {
@@ -68,3 +300,10 @@ impl From<serde_json::Error> for Error {
         Error::Json(err)
     }
 }
+
+#[cfg(feature = "websocket")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(err))
+    }
+}
@@ -1,4 +1,12 @@
-use serde::{Serialize, de::DeserializeOwned};
+use super::{Error, hello};
+use log::info;
+use serde::Serialize;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+    value::MapAccessDeserializer,
+};
+use std::borrow::Cow;
+use std::marker::PhantomData;
 
 pub trait Packet {
     fn kind() -> String;
@@ -10,10 +18,201 @@ pub trait Request: Serialize + Packet {
 
 pub trait Response: DeserializeOwned + Packet {}
 
-impl<P: Response> Response for Result<P, String> {}
+/// A response the game can refuse, with `E` saying why.
+impl<P: Response, E: DeserializeOwned> Response for Result<P, E> {}
 
-impl<P: Response> Packet for Result<P, String> {
+impl<P: Response, E> Packet for Result<P, E> {
     fn kind() -> String {
         "result/".to_string() + &P::kind()
     }
 }
+
+/// Frames a packet as `kind!body`.
+pub(crate) fn encode<T: Serialize + Packet>(msg: &T) -> Result<String, Error> {
+    let body = serde_json::to_string(msg)?;
+    Ok(format!("{}!{}", T::kind(), body))
+}
+
+/// Frames a packet like [`encode`], adding `epoch` to the body so the game can
+/// tell which state the request was made from.
+pub(crate) fn encode_at<T: Serialize + Packet>(msg: &T, epoch: u64) -> Result<String, Error> {
+    let body = serde_json::to_string(&AtEpoch { msg, epoch })?;
+    Ok(format!("{}!{}", T::kind(), body))
+}
+
+#[derive(Serialize)]
+struct AtEpoch<'m, T> {
+    #[serde(flatten)]
+    msg: &'m T,
+    epoch: u64,
+}
+
+/// Adds a request id to a framed packet, giving `kind#id!body`.
+pub(crate) fn tag(packet: &str, id: u64) -> String {
+    match packet.split_once('!') {
+        Some((kind, body)) => format!("{kind}#{id}!{body}"),
+        None => format!("{packet}#{id}"),
+    }
+}
+
+/// Splits the request id off a `kind#id!body` packet, giving back the id and
+/// the plain `kind!body` packet. Packets without a valid id are returned as is.
+pub(crate) fn untag(packet: String) -> (Option<u64>, String) {
+    let kind = packet
+        .split_once('!')
+        .map_or(packet.as_str(), |(kind, _)| kind);
+    let Some((plain, id)) = kind.split_once('#') else {
+        return (None, packet);
+    };
+    let Ok(id) = id.parse() else {
+        return (None, packet);
+    };
+    let untagged = format!("{plain}{}", &packet[kind.len()..]);
+    (Some(id), untagged)
+}
+
+/// Parses a `kind!body` packet, checking that it is of the expected kind.
+///
+/// Versions are negotiated once by the hello exchange, so bodies no longer need
+/// to carry one. If a body still has a top level `version` field, it must be
+/// compatible. The body is parsed straight into `R`, skipping over the version
+/// on the way, so large responses are never buffered as a [`serde_json::Value`].
+pub(crate) fn decode<R: DeserializeOwned + Packet>(packet: &str) -> Result<R, Error> {
+    decode_at(packet).map(|(response, _)| response)
+}
+
+/// [`decode`], exposed for the `decode` benchmark only.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub fn bench_decode<R: DeserializeOwned + Packet>(packet: &str) -> Result<R, Error> {
+    decode(packet)
+}
+
+/// Parses a packet like [`decode`], also returning the state epoch from the
+/// top level `epoch` field of the body, if the game sent one.
+pub(crate) fn decode_at<R: DeserializeOwned + Packet>(
+    packet: &str,
+) -> Result<(R, Option<u64>), Error> {
+    let mut split = packet.splitn(2, '!');
+    let kind = split
+        .next()
+        .ok_or(Error::Message(Cow::Borrowed("Received packet has no kind")))?;
+    let body = split
+        .next()
+        .ok_or(Error::Message(Cow::Borrowed("Received packet has no body")))?;
+
+    if kind != R::kind() {
+        return Err(Error::Message(Cow::Owned(format!(
+            "Expected response kind {}, got {}",
+            R::kind(),
+            kind
+        ))));
+    }
+
+    info!("Received: {body}");
+
+    if !body.trim_start().starts_with('{') {
+        return Ok((serde_json::from_str(body)?, None));
+    }
+
+    let mut mismatch = None;
+    let mut epoch = None;
+    let mut de = serde_json::Deserializer::from_str(body);
+    let response = Versioned {
+        mismatch: &mut mismatch,
+        epoch: &mut epoch,
+        _marker: PhantomData::<R>,
+    }
+    .deserialize(&mut de)
+    .and_then(|response| de.end().map(|_| response));
+    if let Some(theirs) = mismatch {
+        return Err(hello::mismatch(theirs));
+    }
+    Ok((response?, epoch))
+}
+
+/// Deserializes a JSON object into `R`, checking and hiding its `version` field,
+/// and taking out its `epoch` field.
+struct Versioned<'v, R> {
+    mismatch: &'v mut Option<String>,
+    epoch: &'v mut Option<u64>,
+    _marker: PhantomData<R>,
+}
+
+impl<'de, R: de::Deserialize<'de>> DeserializeSeed<'de> for Versioned<'_, R> {
+    type Value = R;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<R, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, R: de::Deserialize<'de>> Visitor<'de> for Versioned<'_, R> {
+    type Value = R;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a response object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<R, A::Error> {
+        let mut map = SkipVersion {
+            inner: map,
+            mismatch: self.mismatch,
+            epoch: self.epoch,
+        };
+        let response = R::deserialize(MapAccessDeserializer::new(&mut map))?;
+        // Enum responses only consume their variant, so a version may still follow.
+        while map.next_key::<de::IgnoredAny>()?.is_some() {
+            map.next_value::<de::IgnoredAny>()?;
+        }
+        Ok(response)
+    }
+}
+
+/// Passes map entries through, except for `version`, which is checked against
+/// ours, and `epoch`, which is kept aside.
+struct SkipVersion<'v, A> {
+    inner: A,
+    mismatch: &'v mut Option<String>,
+    epoch: &'v mut Option<u64>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for SkipVersion<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        while let Some(key) = self.inner.next_key::<Cow<'de, str>>()? {
+            if key == "epoch" {
+                *self.epoch = Some(self.inner.next_value()?);
+                continue;
+            }
+            if key != "version" {
+                return seed.deserialize(key.into_deserializer()).map(Some);
+            }
+            let version: serde_json::Value = self.inner.next_value()?;
+            let theirs = version.as_str().unwrap_or_default();
+            match semver::Version::parse(theirs) {
+                Ok(v) if hello::compatible(&hello::ours(), &v) => {}
+                _ => {
+                    *self.mismatch = Some(match version.as_str() {
+                        Some(theirs) => theirs.to_string(),
+                        None => version.to_string(),
+                    });
+                    return Err(de::Error::custom("incompatible protocol version"));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,51 @@
+//! Joining a room on a relay, for when neither the controller nor the game can
+//! accept connections. Both sides connect out to the relay, each sends a
+//! [`Join`] as its first packet, and the relay pairs them by room code.
+
+use super::{Error, protocol, protocol::Packet};
+
+use serde::{Deserialize, Serialize};
+
+/// Which end of a game a relay leg belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Game,
+    Controller,
+}
+
+impl Role {
+    /// The role a leg must have to be paired with this one.
+    pub fn partner(self) -> Role {
+        match self {
+            Role::Game => Role::Controller,
+            Role::Controller => Role::Game,
+        }
+    }
+}
+
+/// First packet on a relay leg. Not answered; the relay starts forwarding
+/// once the other side of the room has joined.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Join {
+    pub room: String,
+    pub role: Role,
+}
+
+impl Packet for Join {
+    fn kind() -> String {
+        "relay/join".to_string()
+    }
+}
+
+impl Join {
+    /// Frames this join as the first packet of a leg.
+    pub fn encode(&self) -> Result<String, Error> {
+        protocol::encode(self)
+    }
+
+    /// Parses the first packet of a leg.
+    pub fn decode(packet: &str) -> Result<Self, Error> {
+        protocol::decode(packet)
+    }
+}
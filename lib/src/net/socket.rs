@@ -0,0 +1,229 @@
+#[cfg(feature = "tls")]
+use super::TlsConfig;
+#[cfg(feature = "websocket")]
+use super::WebSocketTransport;
+use super::{Connection, ConnectionConfig, Error, StreamTransport, Transport};
+
+use log::warn;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+/// Listens for games and hands out an [`Incoming`] stream for each one.
+pub struct Socket {
+    listener: Listener,
+    config: ConnectionConfig,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>,
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: PathBuf,
+    },
+    #[cfg(feature = "websocket")]
+    WebSocket(TcpListener),
+}
+
+impl Socket {
+    pub async fn bind(
+        host: impl AsRef<str>,
+        port: u16,
+        config: ConnectionConfig,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind(format!("{}:{}", host.as_ref(), port)).await?;
+        Ok(Self {
+            listener: Listener::Tcp(listener),
+            config,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// Binds a Unix domain socket at `path`. The socket file is removed again
+    /// when the [`Socket`] is dropped.
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl AsRef<Path>, config: ConnectionConfig) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self {
+            listener: Listener::Unix { listener, path },
+            config,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// Binds a TCP socket that expects games to connect over WebSocket, with
+    /// one packet per text frame.
+    #[cfg(feature = "websocket")]
+    pub async fn bind_websocket(
+        host: impl AsRef<str>,
+        port: u16,
+        config: ConnectionConfig,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind(format!("{}:{}", host.as_ref(), port)).await?;
+        Ok(Self {
+            listener: Listener::WebSocket(listener),
+            config,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// Wraps every accepted stream in TLS before any packet is exchanged.
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Waits for a game to connect. TLS and WebSocket handshakes are left to
+    /// [`Incoming::upgrade`], so a peer that connects and then says nothing
+    /// only holds up its own connection.
+    pub async fn accept(&mut self) -> Result<Incoming, Error> {
+        let stream = match self.config.accept_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.accept_stream())
+                .await
+                .map_err(|_| Error::Timeout)??,
+            None => self.accept_stream().await?,
+        };
+        Ok(Incoming {
+            stream,
+            upgrader: Upgrader {
+                config: self.config.clone(),
+                #[cfg(feature = "tls")]
+                tls: self.tls.clone(),
+            },
+        })
+    }
+
+    /// The settings every accepted connection is created with.
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// The TCP address listened on, or `None` for a Unix domain socket.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix { .. } => None,
+            #[cfg(feature = "websocket")]
+            Listener::WebSocket(listener) => listener.local_addr().ok(),
+        }
+    }
+
+    async fn accept_stream(&mut self) -> Result<RawStream, Error> {
+        match &self.listener {
+            Listener::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok(RawStream::Tcp(stream))
+            }
+            #[cfg(unix)]
+            Listener::Unix { listener, .. } => {
+                let (stream, _) = listener.accept().await?;
+                Ok(RawStream::Unix(stream))
+            }
+            #[cfg(feature = "websocket")]
+            Listener::WebSocket(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok(RawStream::WebSocket(stream))
+            }
+        }
+    }
+}
+
+/// A stream accepted by a [`Socket`], before any TLS or WebSocket handshake.
+pub(crate) struct Incoming {
+    stream: RawStream,
+    upgrader: Upgrader,
+}
+
+enum RawStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+    #[cfg(feature = "websocket")]
+    WebSocket(TcpStream),
+}
+
+impl Incoming {
+    /// Runs the TLS and WebSocket handshakes the socket was set up with,
+    /// bounded together by [`ConnectionConfig::handshake_timeout`].
+    pub(crate) async fn upgrade(self) -> Result<Connection, Error> {
+        let Incoming { stream, upgrader } = self;
+        let transport = tokio::time::timeout(
+            upgrader.config.handshake_timeout,
+            upgrader.transport(stream),
+        )
+        .await
+        .map_err(|_| Error::Timeout)??;
+        Ok(Connection::new(transport, &upgrader.config))
+    }
+}
+
+/// The handshakes an [`Incoming`] stream goes through, copied from its [`Socket`].
+struct Upgrader {
+    config: ConnectionConfig,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>,
+}
+
+impl Upgrader {
+    async fn transport(&self, stream: RawStream) -> Result<Box<dyn Transport>, Error> {
+        match stream {
+            RawStream::Tcp(stream) => self.line_transport(stream).await,
+            #[cfg(unix)]
+            RawStream::Unix(stream) => self.line_transport(stream).await,
+            #[cfg(feature = "websocket")]
+            RawStream::WebSocket(stream) => self.websocket_transport(stream).await,
+        }
+    }
+
+    async fn line_transport<S>(&self, stream: S) -> Result<Box<dyn Transport>, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let stream = tls.accept(stream).await?;
+            return Ok(Box::new(StreamTransport::new(stream, &self.config)));
+        }
+        Ok(Box::new(StreamTransport::new(stream, &self.config)))
+    }
+
+    #[cfg(feature = "websocket")]
+    async fn websocket_transport<S>(&self, stream: S) -> Result<Box<dyn Transport>, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let stream = tls.accept(stream).await?;
+            let ws = tokio_tungstenite::accept_async(stream).await?;
+            return Ok(Box::new(WebSocketTransport::new(ws, &self.config)));
+        }
+        let ws = tokio_tungstenite::accept_async(stream).await?;
+        Ok(Box::new(WebSocketTransport::new(ws, &self.config)))
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Listener::Unix { path, .. } = &self.listener
+            && let Err(e) = std::fs::remove_file(path)
+        {
+            warn!("Failed to remove socket file {}: {e}", path.display());
+        }
+    }
+}
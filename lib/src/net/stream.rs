@@ -1,33 +1,20 @@
-use serde::Serialize;
 use std::borrow::Cow;
-use std::str::FromStr;
 use tokio::{
-    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
-    net::{
-        TcpStream,
-        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    io::{
+        AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, BufWriter,
     },
     sync::{mpsc, oneshot},
     task::JoinHandle,
     time::{Instant, sleep},
 };
 
-use super::Error;
-use super::protocol::Packet; // Assuming Error is in super
+use super::transport::{BoxFuture, Transport};
+use super::{ConnectionConfig, Error};
 
 use log::{debug, error, info, trace, warn};
-use serde::de::DeserializeOwned;
 use std::time::Duration;
 // --- Constants for Heartbeat and Connection Logic ---
 
-/// Size of the MPSC channels used for communication between the main struct and the background task.
-const CHANNEL_BUFFER_SIZE: usize = 32;
-/// Duration of inactivity (no packets received or sent) before a ping is sent.
-const INACTIVITY_TIMEOUT_SECS: u64 = 7;
-/// Duration to wait for a response (any packet) after sending a ping before retrying.
-const PING_RESPONSE_TIMEOUT_SECS: u64 = 3;
-/// Maximum number of ping retries before closing the connection due to timeout.
-const MAX_PING_RETRIES: u8 = 3;
 /// The exact string format for a ping packet (including delimiter).
 const PING_PACKET: &str = "ping!";
 /// The exact string format for a pong packet (including delimiter).
@@ -35,12 +22,15 @@ const PONG_PACKET: &str = "pong!";
 /// A practically infinite duration used to disable timers initially.
 const FOREVER_DURATION: Duration = Duration::from_secs(u64::MAX);
 
-/// Represents a TCP stream with an associated background task handling
+/// Represents a byte stream with an associated background task handling
 /// raw I/O, packet framing (kind!body\\n), and a heartbeat mechanism.
 ///
-/// Communication between the public methods (`send`, `recv`) and the background task
+/// Any `AsyncRead + AsyncWrite` stream can be used, so TCP, Unix sockets and
+/// in-process pipes all get the same framing and heartbeat.
+///
+/// Communication between the [`Transport`] methods (`send`, `recv`) and the background task
 /// occurs via asynchronous channels.
-pub struct TcpStreamExt {
+pub struct StreamTransport {
     /// Sends fully formatted packet strings (`kind!body`) to the background task for writing.
     tx_outgoing: mpsc::Sender<String>,
     /// Receives results containing either successfully read and framed packets (`kind!body`)
@@ -54,15 +44,18 @@ pub struct TcpStreamExt {
     close_tx: Option<oneshot::Sender<()>>,
 }
 
-impl TcpStreamExt {
-    pub fn new(stream: TcpStream) -> Self {
-        let (reader_half, writer_half) = stream.into_split();
+impl StreamTransport {
+    pub fn new<S>(stream: S, config: &ConnectionConfig) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (reader_half, writer_half) = tokio::io::split(stream);
         let reader = BufReader::new(reader_half);
         let writer = BufWriter::new(writer_half);
 
-        let (tx_outgoing, rx_outgoing) = mpsc::channel::<String>(CHANNEL_BUFFER_SIZE);
+        let (tx_outgoing, rx_outgoing) = mpsc::channel::<String>(config.channel_buffer_size.get());
         let (tx_incoming, rx_incoming) =
-            mpsc::channel::<Result<String, Error>>(CHANNEL_BUFFER_SIZE);
+            mpsc::channel::<Result<String, Error>>(config.channel_buffer_size.get());
         let (close_tx, close_rx) = oneshot::channel::<()>();
 
         let task_handle = tokio::spawn(run_connection(
@@ -71,6 +64,7 @@ impl TcpStreamExt {
             rx_outgoing,
             tx_incoming.clone(),
             close_rx,
+            config.clone(),
         ));
 
         Self {
@@ -80,63 +74,28 @@ impl TcpStreamExt {
             close_tx: Some(close_tx),
         }
     }
+}
 
-    pub async fn send<T: Serialize + Packet>(&mut self, msg: T) -> Result<(), Error> {
-        let body = serde_json::to_string(&msg)?;
-        let packet_str = format!("{}!{}", T::kind(), body);
-        self.tx_outgoing.send(packet_str).await.map_err(|_| {
-            Error::Channel(Cow::Borrowed("Failed to send packet to background task"))
-        })?;
-        Ok(())
+impl Transport for StreamTransport {
+    fn send(&mut self, packet: String) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.tx_outgoing.send(packet).await.map_err(|_| {
+                Error::Channel(Cow::Borrowed("Failed to send packet to background task"))
+            })
+        })
     }
 
-    pub async fn recv<R: DeserializeOwned + Packet>(&mut self) -> Result<R, Error> {
-        let received_result = self
-            .rx_incoming
-            .recv()
-            .await
-            .ok_or(Error::ConnectionClosed)?;
-
-        let buf = received_result?;
-
-        let mut split = buf.splitn(2, '!');
-        let kind = split
-            .next()
-            .ok_or(Error::Message(Cow::Borrowed("Received packet has no kind")))?;
-        let body = split
-            .next()
-            .ok_or(Error::Message(Cow::Borrowed("Received packet has no body")))?;
-
-        if kind != R::kind() {
-            return Err(Error::Message(Cow::Owned(format!(
-                "Expected response kind {}, got {}",
-                R::kind(),
-                kind
-            ))));
-        }
-
-        info!("Received: {body}");
-
-        // Extract version from json and ensure it matches crate version
-        let mut body = serde_json::Value::from_str(body)?;
-        let body = match body.as_object_mut() {
-            Some(b) => b,
-            None => return Err(Error::VersionMismatch),
-        };
-        if let Some(version) = body.remove("version")
-            && version != env!("CARGO_PKG_VERSION")
-        {
-            return Err(Error::VersionMismatch);
-        }
-        let body = serde_json::to_string(&body)?;
-        let body = body.as_str();
-
-        let response: R = serde_json::from_str(body)?;
-        Ok(response)
+    fn recv(&mut self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async move {
+            self.rx_incoming
+                .recv()
+                .await
+                .ok_or(Error::ConnectionClosed)?
+        })
     }
 }
 
-impl Drop for TcpStreamExt {
+impl Drop for StreamTransport {
     fn drop(&mut self) {
         if let Some(sender) = self.close_tx.take() {
             let _ = sender.send(());
@@ -144,29 +103,34 @@ impl Drop for TcpStreamExt {
     }
 }
 
-/// The core background task that handles TCP reading, writing, packet framing,
+/// The core background task that handles stream reading, writing, packet framing,
 /// and the ping/pong heartbeat mechanism.
 ///
 /// It runs in a loop, using `tokio::select!` to concurrently manage:
-/// 1. Receiving outgoing packets from `TcpStreamExt::send` via `rx_outgoing`.
-/// 2. Reading incoming data from the `TcpStream` (`reader`).
+/// 1. Receiving outgoing packets from `Transport::send` via `rx_outgoing`.
+/// 2. Reading incoming data from the stream (`reader`).
 /// 3. Handling ping responses and forwarding other data via `tx_incoming`.
 /// 4. Tracking inactivity and sending pings.
 /// 5. Tracking ping responses and handling retries/timeouts.
 /// 6. Listening for a shutdown signal via `close_rx`.
-async fn run_connection(
-    mut reader: BufReader<OwnedReadHalf>,
-    mut writer: BufWriter<OwnedWriteHalf>,
+async fn run_connection<R, W>(
+    mut reader: R,
+    mut writer: W,
     mut rx_outgoing: mpsc::Receiver<String>, // Messages to send from Self::send
     tx_incoming: mpsc::Sender<Result<String, Error>>, // Framed messages or errors back to Self::recv
     mut close_rx: oneshot::Receiver<()>,              // Signal to close from Drop
-) {
+    config: ConnectionConfig,
+) where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
     info!("Connection task started.");
     // Buffer for reading lines from the socket.
     let mut line_buf = String::new();
-    // Durations for timers, loaded from constants.
-    let inactivity_timeout = Duration::from_secs(INACTIVITY_TIMEOUT_SECS);
-    let ping_response_timeout = Duration::from_secs(PING_RESPONSE_TIMEOUT_SECS);
+    // Durations for timers, loaded from the connection config.
+    let inactivity_timeout = config.inactivity_timeout;
+    let ping_response_timeout = config.ping_response_timeout;
+    let max_ping_retries = config.max_ping_retries;
 
     // --- Timers ---
     // Timer for detecting inactivity (no sends or receives).
@@ -186,10 +150,10 @@ async fn run_connection(
     loop {
         tokio::select! {
             // `biased;` ensures that the shutdown signal is checked first in each loop iteration,
-            // allowing for prompt termination when `TcpStreamExt` is dropped.
+            // allowing for prompt termination when `StreamTransport` is dropped.
             biased;
 
-            // 1. Check for shutdown signal from `TcpStreamExt::drop`.
+            // 1. Check for shutdown signal from `StreamTransport::drop`.
             _ = &mut close_rx => {
                 // Got close signal.
                 info!("Received shutdown signal.");
@@ -247,7 +211,7 @@ async fn run_connection(
                                 debug!("Received data packet, forwarding upstream.");
                                 let owned_line = received_line.to_string();
                                 if tx_incoming.send(Ok(owned_line)).await.is_err() {
-                                    // Upstream receiver (`TcpStreamExt::recv`) has been dropped. Connection is useless.
+                                    // Upstream receiver (`StreamTransport::recv`) has been dropped. Connection is useless.
                                     info!("Upstream receiver closed, shutting down connection task.");
                                     break;
                                 }
@@ -265,7 +229,7 @@ async fn run_connection(
                 }
             }
 
-            // 3. Send an outgoing packet requested by `TcpStreamExt::send`.
+            // 3. Send an outgoing packet requested by `StreamTransport::send`.
             Some(packet_str) = rx_outgoing.recv() => {
                 trace!("Received packet from upstream to send: '{packet_str}'");
                 // Add newline because the reader side uses `read_line`.
@@ -291,10 +255,10 @@ async fn run_connection(
 
             // 4. Inactivity timer fired.
             _ = &mut inactivity_timer => {
-                // No packets sent or received for INACTIVITY_TIMEOUT_SECS.
+                // No packets sent or received for the inactivity timeout.
                 // Send the first ping if we aren't already in a ping/pong cycle.
                 if pings_sent_without_response == 0 {
-                    debug!("Inactivity detected, sending PING (Attempt 1/{MAX_PING_RETRIES})");
+                    debug!("Inactivity detected, sending PING (Attempt 1/{max_ping_retries})");
                     if let Err(e) = writer.write_all(format!("{PING_PACKET}\n").as_bytes()).await {
                         error!("Failed to send PING (Attempt 1): {e}");
                         let _ = tx_incoming.send(Err(e.into())).await;
@@ -321,18 +285,18 @@ async fn run_connection(
 
             // 5. Ping response timer fired (only active if pings_sent_without_response > 0).
             _ = &mut ping_timer, if pings_sent_without_response > 0 => {
-                warn!("No response received after PING (Attempt {pings_sent_without_response}/{MAX_PING_RETRIES})");
-                // Waited PING_RESPONSE_TIMEOUT_SECS for *any* packet after sending a ping, but received none.
-                if pings_sent_without_response >= MAX_PING_RETRIES {
+                warn!("No response received after PING (Attempt {pings_sent_without_response}/{max_ping_retries})");
+                // Waited the ping response timeout for *any* packet after sending a ping, but received none.
+                if pings_sent_without_response >= max_ping_retries {
                     // Exceeded max retries, declare timeout.
-                    error!("Ping timeout after {MAX_PING_RETRIES} retries. Closing connection.");
+                    error!("Ping timeout after {max_ping_retries} retries. Closing connection.");
                     let _ = tx_incoming.send(Err(Error::Timeout)).await;
                     break;
                 }
 
                 // Send another ping (retry).
                 let next_attempt = pings_sent_without_response + 1;
-                debug!("Sending PING (Attempt {next_attempt}/{MAX_PING_RETRIES})");
+                debug!("Sending PING (Attempt {next_attempt}/{max_ping_retries})");
                 if let Err(e) = writer.write_all(format!("{PING_PACKET}\n").as_bytes()).await {
                     error!("Failed to send PING (Attempt {next_attempt}): {e}");
                     let _ = tx_incoming.send(Err(e.into())).await;
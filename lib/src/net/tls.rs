@@ -0,0 +1,64 @@
+use super::Error;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{self, ServerConfig, crypto::ring},
+    server::TlsStream,
+};
+
+/// Server side TLS settings for a [`Remotro`](crate::Remotro). The game
+/// connects as a TLS client, and framing and heartbeats run unchanged inside
+/// the encrypted stream.
+#[derive(Clone)]
+pub struct TlsConfig {
+    acceptor: TlsAcceptor,
+}
+
+impl TlsConfig {
+    /// Loads a PEM certificate chain and a PEM private key (PKCS#1, PKCS#8 or SEC1).
+    pub fn from_pem_files(cert: impl AsRef<Path>, key: impl AsRef<Path>) -> Result<Self, Error> {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(key)?))?.ok_or(
+            Error::Message(std::borrow::Cow::Borrowed(
+                "No private key found in PEM file",
+            )),
+        )?;
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        Ok(Self::from_server_config(Arc::new(config)))
+    }
+
+    /// Uses an already built rustls config, e.g. to require client certificates.
+    pub fn from_server_config(config: Arc<ServerConfig>) -> Self {
+        Self {
+            acceptor: TlsAcceptor::from(config),
+        }
+    }
+
+    pub(crate) async fn accept<S>(&self, stream: S) -> Result<TlsStream<S>, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        Ok(self.acceptor.accept(stream).await?)
+    }
+}
+
+impl std::fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConfig").finish_non_exhaustive()
+    }
+}
+
+impl From<rustls::Error> for Error {
+    fn from(err: rustls::Error) -> Self {
+        Error::Tls(err)
+    }
+}
@@ -0,0 +1,146 @@
+//! Recording every packet of a connection to a JSON Lines transcript, for
+//! debugging protocol problems. Enable it with
+//! [`RemotroBuilder::record`](crate::RemotroBuilder::record) or
+//! [`ConnectionConfig::recorder`](super::ConnectionConfig::recorder).
+//!
+//! Each line is one [`Record`]. Heartbeats are handled by the transport and
+//! are not recorded.
+
+use super::{ConnectionConfig, Error};
+
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+
+/// Which way a packet went, seen from the client.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+/// One packet in a transcript.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Record {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Distinguishes connections that share a recorder, in order of creation.
+    pub connection: u64,
+    pub direction: Direction,
+    pub kind: String,
+    /// Request id, if the game supports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    /// The parsed body, or the raw body as a string if it is not valid JSON.
+    pub body: serde_json::Value,
+    /// For responses, milliseconds since the request they answer was sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
+}
+
+/// Appends [`Record`]s to a transcript file from a background task, so
+/// recording never blocks a connection. If the file can't keep up and the
+/// queue is full, records are dropped and counted in [`Recorder::dropped`].
+/// Cloning gives another handle to the same file, and connections sharing a
+/// recorder are told apart by [`Record::connection`].
+#[derive(Clone)]
+pub struct Recorder {
+    tx: mpsc::Sender<Record>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Recorder {
+    /// Opens `path` for appending, creating it if needed. Up to
+    /// `config.channel_buffer_size` records are queued for writing. Must be
+    /// called from within a Tokio runtime.
+    pub async fn create(path: impl AsRef<Path>, config: &ConnectionConfig) -> Result<Self, Error> {
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        let (tx, rx) = mpsc::channel(config.channel_buffer_size.get());
+        tokio::spawn(run_writer(BufWriter::new(file), rx));
+        Ok(Self {
+            tx,
+            dropped: Arc::default(),
+        })
+    }
+
+    /// Records left out of the transcript because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record(
+        &self,
+        connection: u64,
+        direction: Direction,
+        packet: &str,
+        id: Option<u64>,
+        latency: Option<Duration>,
+    ) {
+        let (kind, body) = packet.split_once('!').unwrap_or((packet, ""));
+        let body = serde_json::from_str(body)
+            .unwrap_or_else(|_| serde_json::Value::String(body.to_string()));
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let record = Record {
+            timestamp_ms,
+            connection,
+            direction,
+            kind: kind.to_string(),
+            id,
+            body,
+            latency_ms: latency.map(|latency| latency.as_secs_f64() * 1000.0),
+        };
+        // The writer only stops on a write error, so a closed queue has
+        // already been logged.
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(record)
+            && self.dropped.fetch_add(1, Ordering::Relaxed) == 0
+        {
+            warn!("Transcript is falling behind, dropping records");
+        }
+    }
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+/// Writes records as they arrive, flushing whenever there is nothing queued.
+async fn run_writer(mut writer: BufWriter<tokio::fs::File>, mut rx: mpsc::Receiver<Record>) {
+    while let Some(record) = rx.recv().await {
+        let mut line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                error!("Failed to serialize transcript record: {e}");
+                continue;
+            }
+        };
+        line.push('\n');
+        if let Err(e) = writer.write_all(line.as_bytes()).await {
+            error!("Failed to write transcript: {e}");
+            return;
+        }
+        if rx.is_empty()
+            && let Err(e) = writer.flush().await
+        {
+            error!("Failed to flush transcript: {e}");
+            return;
+        }
+    }
+    let _ = writer.flush().await;
+}
@@ -0,0 +1,34 @@
+use super::Error;
+
+use std::future::Future;
+use std::pin::Pin;
+
+/// A boxed future, used so [`Transport`] can be stored as a trait object.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A carrier for framed `kind!body` packets between the client and the game.
+///
+/// Implementations only move whole packets around. Encoding, decoding and
+/// version checks are done by [`Connection`](super::Connection), so anything
+/// that can deliver packets in order can drive a [`Balatro`](crate::balatro::Balatro).
+pub trait Transport: Send {
+    /// Sends a single packet, without any trailing delimiter.
+    fn send(&mut self, packet: String) -> BoxFuture<'_, Result<(), Error>>;
+
+    /// Receives the next packet meant for the application. Anything internal
+    /// to the carrier, such as heartbeats, should not be returned.
+    ///
+    /// Must be cancel safe: dropping the future before it completes must not
+    /// lose a packet, as receives are interrupted to send requests.
+    fn recv(&mut self) -> BoxFuture<'_, Result<String, Error>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&mut self, packet: String) -> BoxFuture<'_, Result<(), Error>> {
+        (**self).send(packet)
+    }
+
+    fn recv(&mut self) -> BoxFuture<'_, Result<String, Error>> {
+        (**self).recv()
+    }
+}
@@ -0,0 +1,223 @@
+use futures_util::{SinkExt, StreamExt};
+use std::borrow::Cow;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::{Instant, sleep},
+};
+use tokio_tungstenite::{
+    WebSocketStream,
+    tungstenite::{Bytes, Message},
+};
+
+use super::transport::{BoxFuture, Transport};
+use super::{ConnectionConfig, Error};
+
+use log::{debug, error, info, trace, warn};
+use std::time::Duration;
+
+/// A practically infinite duration used to disable timers initially.
+const FOREVER_DURATION: Duration = Duration::from_secs(u64::MAX);
+
+/// A WebSocket with an associated background task, carrying one `kind!body`
+/// packet per text frame.
+///
+/// The heartbeat follows the same timings as [`StreamTransport`](super::StreamTransport),
+/// but uses WebSocket ping/pong control frames instead of `ping!`/`pong!` packets,
+/// so proxies in between see an ordinary, healthy WebSocket.
+pub struct WebSocketTransport {
+    /// Sends packet strings (`kind!body`) to the background task for writing.
+    tx_outgoing: mpsc::Sender<String>,
+    /// Receives packets from text frames, or errors from the background task.
+    rx_incoming: mpsc::Receiver<Result<String, Error>>,
+    /// Handle to the background task, kept so it lives as long as the struct.
+    _task_handle: JoinHandle<()>,
+    /// Signals the background task to close the WebSocket on drop.
+    close_tx: Option<oneshot::Sender<()>>,
+}
+
+impl WebSocketTransport {
+    pub fn new<S>(stream: WebSocketStream<S>, config: &ConnectionConfig) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (tx_outgoing, rx_outgoing) = mpsc::channel::<String>(config.channel_buffer_size.get());
+        let (tx_incoming, rx_incoming) =
+            mpsc::channel::<Result<String, Error>>(config.channel_buffer_size.get());
+        let (close_tx, close_rx) = oneshot::channel::<()>();
+
+        let task_handle = tokio::spawn(run_connection(
+            stream,
+            rx_outgoing,
+            tx_incoming,
+            close_rx,
+            config.clone(),
+        ));
+
+        Self {
+            tx_outgoing,
+            rx_incoming,
+            _task_handle: task_handle,
+            close_tx: Some(close_tx),
+        }
+    }
+}
+
+impl Transport for WebSocketTransport {
+    fn send(&mut self, packet: String) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.tx_outgoing.send(packet).await.map_err(|_| {
+                Error::Channel(Cow::Borrowed("Failed to send packet to background task"))
+            })
+        })
+    }
+
+    fn recv(&mut self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async move {
+            self.rx_incoming
+                .recv()
+                .await
+                .ok_or(Error::ConnectionClosed)?
+        })
+    }
+}
+
+impl Drop for WebSocketTransport {
+    fn drop(&mut self) {
+        if let Some(sender) = self.close_tx.take() {
+            let _ = sender.send(());
+        }
+    }
+}
+
+/// Background task for a [`WebSocketTransport`]. Forwards text frames in both
+/// directions and keeps the connection alive with WebSocket pings, giving up
+/// after `max_ping_retries` pings go unanswered.
+async fn run_connection<S>(
+    mut ws: WebSocketStream<S>,
+    mut rx_outgoing: mpsc::Receiver<String>,
+    tx_incoming: mpsc::Sender<Result<String, Error>>,
+    mut close_rx: oneshot::Receiver<()>,
+    config: ConnectionConfig,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    info!("WebSocket task started.");
+    let inactivity_timeout = config.inactivity_timeout;
+    let ping_response_timeout = config.ping_response_timeout;
+    let max_ping_retries = config.max_ping_retries;
+
+    let inactivity_timer = sleep(inactivity_timeout);
+    let ping_timer = sleep(FOREVER_DURATION);
+    tokio::pin!(inactivity_timer);
+    tokio::pin!(ping_timer);
+
+    let mut pings_sent_without_response: u8 = 0;
+
+    loop {
+        tokio::select! {
+            biased;
+
+            _ = &mut close_rx => {
+                info!("Received shutdown signal.");
+                let _ = ws.close(None).await;
+                break;
+            }
+
+            message = ws.next() => {
+                let message = match message {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => {
+                        error!("WebSocket read error: {e}");
+                        let _ = tx_incoming.send(Err(e.into())).await;
+                        break;
+                    }
+                    None => {
+                        info!("WebSocket closed by peer.");
+                        break;
+                    }
+                };
+                // Any frame from the peer proves it is alive.
+                inactivity_timer.as_mut().reset(Instant::now() + inactivity_timeout);
+                pings_sent_without_response = 0;
+
+                match message {
+                    Message::Text(text) => {
+                        debug!("Received data packet, forwarding upstream.");
+                        if tx_incoming.send(Ok(text.to_string())).await.is_err() {
+                            info!("Upstream receiver closed, shutting down WebSocket task.");
+                            break;
+                        }
+                    }
+                    Message::Ping(_) => {
+                        // tungstenite queues the pong itself, it only needs flushing.
+                        debug!("Received PING, flushing PONG.");
+                        if let Err(e) = ws.flush().await {
+                            error!("Failed to flush PONG: {e}");
+                            let _ = tx_incoming.send(Err(e.into())).await;
+                            break;
+                        }
+                    }
+                    Message::Pong(_) => debug!("Received PONG."),
+                    Message::Close(frame) => {
+                        info!("WebSocket closed by peer: {frame:?}");
+                        break;
+                    }
+                    Message::Binary(_) | Message::Frame(_) => {
+                        warn!("Ignoring non-text WebSocket frame.");
+                    }
+                }
+            }
+
+            Some(packet_str) = rx_outgoing.recv() => {
+                trace!("Sending packet: '{packet_str}'");
+                if let Err(e) = ws.send(Message::text(packet_str)).await {
+                    error!("WebSocket write error: {e}");
+                    let _ = tx_incoming.send(Err(e.into())).await;
+                    break;
+                }
+                inactivity_timer.as_mut().reset(Instant::now() + inactivity_timeout);
+                pings_sent_without_response = 0;
+            }
+
+            _ = &mut inactivity_timer, if pings_sent_without_response == 0 => {
+                debug!("Inactivity detected, sending PING (Attempt 1/{max_ping_retries})");
+                if let Err(e) = ws.send(Message::Ping(Bytes::new())).await {
+                    error!("Failed to send PING (Attempt 1): {e}");
+                    let _ = tx_incoming.send(Err(e.into())).await;
+                    break;
+                }
+                pings_sent_without_response = 1;
+                ping_timer.as_mut().reset(Instant::now() + ping_response_timeout);
+                inactivity_timer.as_mut().reset(Instant::now() + inactivity_timeout);
+            }
+
+            _ = &mut ping_timer, if pings_sent_without_response > 0 => {
+                warn!("No response received after PING (Attempt {pings_sent_without_response}/{max_ping_retries})");
+                if pings_sent_without_response >= max_ping_retries {
+                    error!("Ping timeout after {max_ping_retries} retries. Closing connection.");
+                    let _ = tx_incoming.send(Err(Error::Timeout)).await;
+                    break;
+                }
+                let next_attempt = pings_sent_without_response + 1;
+                debug!("Sending PING (Attempt {next_attempt}/{max_ping_retries})");
+                if let Err(e) = ws.send(Message::Ping(Bytes::new())).await {
+                    error!("Failed to send PING (Attempt {next_attempt}): {e}");
+                    let _ = tx_incoming.send(Err(e.into())).await;
+                    break;
+                }
+                pings_sent_without_response = next_attempt;
+                ping_timer.as_mut().reset(Instant::now() + ping_response_timeout);
+                inactivity_timer.as_mut().reset(Instant::now() + inactivity_timeout);
+            }
+
+            else => {
+                info!("Select loop yielded no active branch, shutting down.");
+                break;
+            }
+        }
+    }
+    info!("WebSocket task finished.");
+    let _ = tx_incoming.send(Err(Error::ConnectionClosed)).await;
+}
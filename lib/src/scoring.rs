@@ -0,0 +1,786 @@
+//! Predicts what a hand will score before it is played.
+//!
+//! [`Play::poker_hand`](crate::balatro::play::Play::poker_hand) only reports the
+//! base chips and mult of the selected poker hand. A [`Scorer`] works out the
+//! rest locally, the way the game does: card chips, enhancements, editions,
+//! seals and retriggers, cards held in hand, and then every Joker from left to
+//! right. The [`Score`] it returns keeps each step, so a prediction can be
+//! explained as well as compared.
+//!
+//! ```no_run
+//! # fn run(play: &remotro::balatro::play::Play<'_>) {
+//! use remotro::scoring::Scorer;
+//!
+//! let scorer = Scorer::for_play(play);
+//! // Compare playing the first two cards against the first five.
+//! let pair = scorer.score_selection(play.hand(), &[0, 1]);
+//! let five = scorer.score_selection(play.hand(), &[0, 1, 2, 3, 4]);
+//! # }
+//! ```
+//!
+//! Effects that depend on chance, like Lucky Cards, Misprint or Bloodstone, are
+//! left out, so the prediction is what the hand is guaranteed to score. Jokers
+//! that grow over the run are applied at their current value, and face down
+//! cards are skipped.
+
+use crate::balatro::{
+    blinds::{Boss, CurrentBlind},
+    deck::{CardEdition, Enhancement, PlayingCard, Rank, Seal, Suit},
+    hud::{CurrentPokerHand, CurrentPokerHands, Hud},
+    jokers::{Joker, JokerEdition, JokerKind},
+    menu::Deck,
+    play::{HandCard, HandEvaluation, HandRules, Play, PokerHandKind},
+};
+use std::borrow::Cow;
+
+/// Everything about the run that affects scoring, other than the cards played.
+#[derive(Debug, Clone)]
+pub struct Scorer<'a> {
+    jokers: &'a [Joker],
+    poker_hands: &'a CurrentPokerHands,
+    rules: HandRules,
+    boss: Option<Boss>,
+    deck: Option<Deck>,
+    hands_left: u32,
+    discards_left: u32,
+    money: u32,
+}
+
+impl<'a> Scorer<'a> {
+    /// Scores with `jokers`, in order, and the poker hand levels in
+    /// `poker_hands`. Defaults to the start of a round against a small blind,
+    /// with 4 hands, 3 discards and no money.
+    pub fn new(jokers: &'a [Joker], poker_hands: &'a CurrentPokerHands) -> Self {
+        Self {
+            jokers,
+            poker_hands,
+            rules: HandRules::from_jokers(jokers),
+            boss: None,
+            deck: None,
+            hands_left: 4,
+            discards_left: 3,
+            money: 0,
+        }
+    }
+
+    /// Scores as the game would on the given play screen.
+    pub fn for_play(play: &'a Play<'_>) -> Self {
+        let mut scorer = Self::new(play.jokers(), &play.run_info().poker_hands)
+            .deck(play.run_info().deck)
+            .hands_left(play.hands())
+            .discards_left(play.discards())
+            .money(play.money());
+        if let CurrentBlind::Boss { kind, .. } = play.blind() {
+            scorer = scorer.boss(*kind);
+        }
+        scorer
+    }
+
+    /// The boss blind being played, for its debuffs and hand restrictions.
+    pub fn boss(mut self, boss: Boss) -> Self {
+        self.boss = Some(boss);
+        self
+    }
+
+    /// The deck of the run. Only the Plasma Deck changes scoring.
+    pub fn deck(mut self, deck: Deck) -> Self {
+        self.deck = Some(deck);
+        self
+    }
+
+    /// Hands left in the round, including the one being scored.
+    pub fn hands_left(mut self, hands: u32) -> Self {
+        self.hands_left = hands;
+        self
+    }
+
+    /// Discards left in the round.
+    pub fn discards_left(mut self, discards: u32) -> Self {
+        self.discards_left = discards;
+        self
+    }
+
+    /// Money held, for Bull.
+    pub fn money(mut self, money: u32) -> Self {
+        self.money = money;
+        self
+    }
+
+    /// Scores playing the selected cards of `hand`, holding the rest. Returns
+    /// `None` if no cards are selected, or only face down ones, or if the game
+    /// left the poker hand they make out of its poker hands.
+    pub fn score(&self, hand: &[HandCard]) -> Option<Score> {
+        let mut played = Vec::new();
+        let mut held = Vec::new();
+        for (index, card) in hand.iter().enumerate() {
+            let Some(playing) = &card.card else {
+                continue;
+            };
+            if card.selected {
+                played.push((index, playing));
+            } else {
+                held.push((index, playing));
+            }
+        }
+        self.run(&played, &held)
+    }
+
+    /// Scores playing the cards of `hand` at `indices`, holding the rest,
+    /// whatever is currently selected. Indices are the same as for
+    /// [`Play::click`].
+    pub fn score_selection(&self, hand: &[HandCard], indices: &[u32]) -> Option<Score> {
+        let hand: Vec<_> = hand
+            .iter()
+            .enumerate()
+            .map(|(index, card)| HandCard {
+                card: card.card.clone(),
+                selected: indices.contains(&(index as u32)),
+            })
+            .collect();
+        self.score(&hand)
+    }
+
+    fn run(
+        &self,
+        played: &[(usize, &PlayingCard)],
+        held: &[(usize, &PlayingCard)],
+    ) -> Option<Score> {
+        let cards: Vec<_> = played.iter().map(|(_, card)| (*card).clone()).collect();
+        let evaluation = PokerHandKind::evaluate_with(&cards, self.rules)?;
+        let splash = self.has(JokerKind::Splash);
+        let scoring: Vec<_> = played
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| splash || evaluation.scoring.contains(position))
+            .map(|(_, card)| *card)
+            .collect();
+
+        let mut round = Round {
+            steps: Vec::new(),
+            chips: 0.0,
+            mult: 0.0,
+        };
+        let kind = evaluation.kind;
+        let current = self.poker_hand(kind)?;
+        let mut base = current.hand.clone();
+        round.set(
+            Source::PokerHand(kind),
+            Effect::Base,
+            base.chips as f64,
+            base.mult as f64,
+        );
+
+        if let Some(boss) = self.refused_by(&current, played.len()) {
+            round.set(Source::Boss(boss), Effect::NotAllowed, 0.0, 0.0);
+            return Some(round.finish(kind, &scoring));
+        }
+        if self.boss == Some(Boss::TheArm) && base.level > 1 {
+            let (chips, mult) = per_level(kind);
+            base.chips = base.chips.saturating_sub(chips);
+            base.mult = base.mult.saturating_sub(mult).max(1);
+            round.set(
+                Source::Boss(Boss::TheArm),
+                Effect::LevelDown,
+                base.chips as f64,
+                base.mult as f64,
+            );
+        }
+        if self.boss == Some(Boss::TheFlint) {
+            let chips = (round.chips * 0.5 + 0.5).floor().max(0.0);
+            let mult = (round.mult * 0.5 + 0.5).floor().max(1.0);
+            round.set(Source::Boss(Boss::TheFlint), Effect::Halve, chips, mult);
+        }
+
+        let photographed = scoring
+            .iter()
+            .find(|(_, card)| !self.debuffed(card) && self.is_face(card))
+            .map(|(index, _)| *index);
+        for (position, &(index, card)) in scoring.iter().enumerate() {
+            if self.debuffed(card) {
+                round.push(Source::Card(index), Effect::Debuffed);
+                continue;
+            }
+            self.score_card(&mut round, index, card, photographed);
+            for source in self.retriggers(index, card, position == 0) {
+                round.push(source, Effect::Retrigger);
+                self.score_card(&mut round, index, card, photographed);
+            }
+        }
+
+        let lowest = held
+            .iter()
+            .filter(|(_, card)| rank(card).is_some())
+            .min_by_key(|(_, card)| rank(card))
+            .map(|(index, _)| *index);
+        for &(index, card) in held {
+            if self.debuffed(card) {
+                continue;
+            }
+            self.hold_card(&mut round, index, card, lowest);
+            let mut retriggers = Vec::new();
+            if card.seal == Some(Seal::Red) {
+                retriggers.push(Source::Card(index));
+            }
+            for (joker, kind) in self.effective() {
+                if kind == JokerKind::Mime {
+                    retriggers.push(Source::Joker(joker));
+                }
+            }
+            for source in retriggers {
+                if self.holds_anything(card, lowest == Some(index)) {
+                    round.push(source, Effect::Retrigger);
+                    self.hold_card(&mut round, index, card, lowest);
+                }
+            }
+        }
+
+        for (index, joker) in self.jokers.iter().enumerate() {
+            let source = Source::Joker(index);
+            match joker.edition {
+                Some(JokerEdition::Foil) => round.chips(source, 50.0),
+                Some(JokerEdition::Holographic) => round.mult(source, 10.0),
+                _ => {}
+            }
+            if let Some(kind) = self.resolve(index) {
+                self.score_joker(
+                    &mut round,
+                    index,
+                    kind,
+                    &evaluation,
+                    played.len(),
+                    &scoring,
+                    held,
+                    &current,
+                );
+            }
+            if joker.edition == Some(JokerEdition::Polychrome) {
+                round.xmult(source, 1.5);
+            }
+        }
+
+        if self.deck == Some(Deck::Plasma) {
+            let balanced = ((round.chips + round.mult) / 2.0).floor();
+            round.set(
+                Source::Deck(Deck::Plasma),
+                Effect::Balance,
+                balanced,
+                balanced,
+            );
+        }
+        Some(round.finish(kind, &scoring))
+    }
+
+    /// Scores one trigger of a played card, then the Jokers that react to it.
+    fn score_card(
+        &self,
+        round: &mut Round,
+        index: usize,
+        card: &PlayingCard,
+        photographed: Option<usize>,
+    ) {
+        let source = Source::Card(index);
+        match card.enhancement {
+            Some(Enhancement::Stone) => round.chips(source, 50.0),
+            Some(Enhancement::Bonus) => round.chips(source, nominal(card.rank) + 30.0),
+            _ => round.chips(source, nominal(card.rank)),
+        }
+        match card.enhancement {
+            Some(Enhancement::Mult) => round.mult(source, 4.0),
+            Some(Enhancement::Glass) => round.xmult(source, 2.0),
+            _ => {}
+        }
+        match card.edition {
+            Some(CardEdition::Foil) => round.chips(source, 50.0),
+            Some(CardEdition::Holographic) => round.mult(source, 10.0),
+            Some(CardEdition::Polychrome) => round.xmult(source, 1.5),
+            None => {}
+        }
+
+        let face = self.is_face(card);
+        let rank = rank(card);
+        for (joker, kind) in self.effective() {
+            let source = Source::Joker(joker);
+            match kind {
+                JokerKind::GreedyJoker if self.has_suit(card, Suit::Diamonds) => {
+                    round.mult(source, 3.0)
+                }
+                JokerKind::LustyJoker if self.has_suit(card, Suit::Hearts) => {
+                    round.mult(source, 3.0)
+                }
+                JokerKind::WrathfulJoker if self.has_suit(card, Suit::Spades) => {
+                    round.mult(source, 3.0)
+                }
+                JokerKind::GluttenousJoker if self.has_suit(card, Suit::Clubs) => {
+                    round.mult(source, 3.0)
+                }
+                JokerKind::Fibonacci
+                    if matches!(
+                        rank,
+                        Some(Rank::Ace | Rank::Two | Rank::Three | Rank::Five | Rank::Eight)
+                    ) =>
+                {
+                    round.mult(source, 8.0)
+                }
+                JokerKind::ScaryFace if face => round.chips(source, 30.0),
+                JokerKind::Smiley if face => round.mult(source, 5.0),
+                JokerKind::EvenSteven
+                    if matches!(
+                        rank,
+                        Some(Rank::Two | Rank::Four | Rank::Six | Rank::Eight | Rank::Ten)
+                    ) =>
+                {
+                    round.mult(source, 4.0)
+                }
+                JokerKind::OddTodd
+                    if matches!(
+                        rank,
+                        Some(Rank::Ace | Rank::Three | Rank::Five | Rank::Seven | Rank::Nine)
+                    ) =>
+                {
+                    round.chips(source, 31.0)
+                }
+                JokerKind::Scholar if rank == Some(Rank::Ace) => {
+                    round.chips(source, 20.0);
+                    round.mult(source, 4.0);
+                }
+                JokerKind::WalkieTalkie if matches!(rank, Some(Rank::Ten | Rank::Four)) => {
+                    round.chips(source, 10.0);
+                    round.mult(source, 4.0);
+                }
+                JokerKind::Arrowhead if self.has_suit(card, Suit::Spades) => {
+                    round.chips(source, 50.0)
+                }
+                JokerKind::OnyxAgate if self.has_suit(card, Suit::Clubs) => round.mult(source, 7.0),
+                JokerKind::Photograph if photographed == Some(index) => round.xmult(source, 2.0),
+                JokerKind::Triboulet if matches!(rank, Some(Rank::King | Rank::Queen)) => {
+                    round.xmult(source, 2.0)
+                }
+                JokerKind::Ancient { suit } if self.has_suit(card, suit) => {
+                    round.xmult(source, 1.5)
+                }
+                JokerKind::Idol { rank: idol, suit }
+                    if rank == Some(idol) && self.has_suit(card, suit) =>
+                {
+                    round.xmult(source, 2.0)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// What retriggers a played card, in the order the game applies them.
+    fn retriggers(&self, index: usize, card: &PlayingCard, first: bool) -> Vec<Source> {
+        let mut retriggers = Vec::new();
+        if card.seal == Some(Seal::Red) {
+            retriggers.push(Source::Card(index));
+        }
+        let rank = rank(card);
+        for (joker, kind) in self.effective() {
+            let times = match kind {
+                JokerKind::Hack
+                    if matches!(
+                        rank,
+                        Some(Rank::Two | Rank::Three | Rank::Four | Rank::Five)
+                    ) =>
+                {
+                    1
+                }
+                JokerKind::SockAndBuskin if self.is_face(card) => 1,
+                JokerKind::HangingChad if first => 2,
+                JokerKind::Dusk if self.hands_left == 1 => 1,
+                JokerKind::Selzer { .. } => 1,
+                _ => 0,
+            };
+            for _ in 0..times {
+                retriggers.push(Source::Joker(joker));
+            }
+        }
+        retriggers
+    }
+
+    /// Scores one trigger of a card held in hand.
+    fn hold_card(
+        &self,
+        round: &mut Round,
+        index: usize,
+        card: &PlayingCard,
+        lowest: Option<usize>,
+    ) {
+        if card.enhancement == Some(Enhancement::Steel) {
+            round.xmult(Source::Card(index), 1.5);
+        }
+        let rank = rank(card);
+        for (joker, kind) in self.effective() {
+            let source = Source::Joker(joker);
+            match kind {
+                JokerKind::Baron if rank == Some(Rank::King) => round.xmult(source, 1.5),
+                JokerKind::ShootTheMoon if rank == Some(Rank::Queen) => round.mult(source, 13.0),
+                JokerKind::RaisedFist if lowest == Some(index) => {
+                    round.mult(source, 2.0 * nominal(card.rank))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether a held card does anything when triggered, so empty retriggers
+    /// are left out of the breakdown.
+    fn holds_anything(&self, card: &PlayingCard, lowest: bool) -> bool {
+        let rank = rank(card);
+        card.enhancement == Some(Enhancement::Steel)
+            || self.effective().any(|(_, kind)| match kind {
+                JokerKind::Baron => rank == Some(Rank::King),
+                JokerKind::ShootTheMoon => rank == Some(Rank::Queen),
+                JokerKind::RaisedFist => lowest,
+                _ => false,
+            })
+    }
+
+    /// Scores a Joker's own effect, after the cards.
+    #[allow(clippy::too_many_arguments)]
+    fn score_joker(
+        &self,
+        round: &mut Round,
+        index: usize,
+        kind: JokerKind,
+        evaluation: &HandEvaluation,
+        played: usize,
+        scoring: &[(usize, &PlayingCard)],
+        held: &[(usize, &PlayingCard)],
+        current: &CurrentPokerHand,
+    ) {
+        let source = Source::Joker(index);
+        let contains = |kind| evaluation.contains.contains(&kind);
+        match kind {
+            JokerKind::Joker => round.mult(source, 4.0),
+            JokerKind::Jolly if contains(PokerHandKind::Pair) => round.mult(source, 8.0),
+            JokerKind::Zany if contains(PokerHandKind::ThreeOfAKind) => round.mult(source, 12.0),
+            JokerKind::Mad if contains(PokerHandKind::TwoPair) => round.mult(source, 10.0),
+            JokerKind::Crazy if contains(PokerHandKind::Straight) => round.mult(source, 12.0),
+            JokerKind::Droll if contains(PokerHandKind::Flush) => round.mult(source, 10.0),
+            JokerKind::Sly if contains(PokerHandKind::Pair) => round.chips(source, 50.0),
+            JokerKind::Wily if contains(PokerHandKind::ThreeOfAKind) => round.chips(source, 100.0),
+            JokerKind::Clever if contains(PokerHandKind::TwoPair) => round.chips(source, 80.0),
+            JokerKind::Devious if contains(PokerHandKind::Straight) => round.chips(source, 100.0),
+            JokerKind::Crafty if contains(PokerHandKind::Flush) => round.chips(source, 80.0),
+            JokerKind::Half if played <= 3 => round.mult(source, 20.0),
+            JokerKind::Banner if self.discards_left > 0 => {
+                round.chips(source, 30.0 * self.discards_left as f64)
+            }
+            JokerKind::MysticSummit if self.discards_left == 0 => round.mult(source, 15.0),
+            JokerKind::Supernova => round.mult(source, (current.played + 1) as f64),
+            JokerKind::Blackboard
+                if held.iter().all(|(_, card)| {
+                    self.has_suit(card, Suit::Spades) || self.has_suit(card, Suit::Clubs)
+                }) =>
+            {
+                round.xmult(source, 3.0)
+            }
+            JokerKind::Acrobat if self.hands_left == 1 => round.xmult(source, 3.0),
+            JokerKind::Bull if self.money > 0 => round.chips(source, 2.0 * self.money as f64),
+            JokerKind::GrosMichel { .. } => round.mult(source, 15.0),
+            JokerKind::Cavendish { .. } => round.xmult(source, 3.0),
+            JokerKind::Duo if contains(PokerHandKind::Pair) => round.xmult(source, 2.0),
+            JokerKind::Trio if contains(PokerHandKind::ThreeOfAKind) => round.xmult(source, 3.0),
+            JokerKind::Family if contains(PokerHandKind::FourOfAKind) => round.xmult(source, 4.0),
+            JokerKind::Order if contains(PokerHandKind::Straight) => round.xmult(source, 3.0),
+            JokerKind::Tribe if contains(PokerHandKind::Flush) => round.xmult(source, 2.0),
+            JokerKind::Stuntman => round.chips(source, 250.0),
+            JokerKind::CardSharp if current.played_round > 0 => round.xmult(source, 3.0),
+            JokerKind::SeeingDouble if self.seeing_double(scoring) => round.xmult(source, 2.0),
+            JokerKind::FlowerPot
+                if [Suit::Diamonds, Suit::Clubs, Suit::Hearts, Suit::Spades]
+                    .into_iter()
+                    .all(|suit| scoring.iter().any(|(_, card)| self.has_suit(card, suit))) =>
+            {
+                round.xmult(source, 3.0)
+            }
+            JokerKind::DriversLicense { cards } if cards >= 16 => round.xmult(source, 3.0),
+            JokerKind::LoyaltyCard { left: 0 } => round.xmult(source, 4.0),
+            JokerKind::Stencil { xmult } if xmult > 1 => round.xmult(source, xmult as f64),
+            JokerKind::Ceremonial { mult }
+            | JokerKind::Abstract { mult }
+            | JokerKind::RideTheBus { mult }
+            | JokerKind::GreenJoker { mult }
+            | JokerKind::RedCard { mult }
+            | JokerKind::FortuneTeller { mult }
+            | JokerKind::Flash { mult }
+            | JokerKind::Popcorn { mult }
+            | JokerKind::Trousers { mult }
+            | JokerKind::Swashbuckler { mult }
+            | JokerKind::Bootstraps { mult }
+            | JokerKind::Erosion { mult }
+                if mult > 0 =>
+            {
+                round.mult(source, mult as f64)
+            }
+            JokerKind::Runner { chips }
+            | JokerKind::IceCream { chips }
+            | JokerKind::BlueJoker { chips }
+            | JokerKind::Square { chips }
+            | JokerKind::Stone { chips }
+            | JokerKind::Castle { chips, .. }
+            | JokerKind::Wee { chips }
+                if chips > 0 =>
+            {
+                round.chips(source, chips as f64)
+            }
+            JokerKind::SteelJoker { xmult }
+            | JokerKind::Constellation { xmult }
+            | JokerKind::Madness { xmult }
+            | JokerKind::Vampire { xmult }
+            | JokerKind::Hologram { xmult }
+            | JokerKind::Obelisk { xmult }
+            | JokerKind::LuckyCat { xmult }
+            | JokerKind::Ramen { xmult }
+            | JokerKind::Campfire { xmult }
+            | JokerKind::Throwback { xmult }
+            | JokerKind::Glass { xmult }
+            | JokerKind::HitTheRoad { xmult }
+            | JokerKind::Caino { xmult }
+            | JokerKind::Yorick { xmult }
+                if xmult > 1.0 =>
+            {
+                round.xmult(source, xmult)
+            }
+            _ => {}
+        }
+    }
+
+    /// Seeing Double wants a scoring Club and a scoring card of another suit.
+    fn seeing_double(&self, scoring: &[(usize, &PlayingCard)]) -> bool {
+        let mut clubs = scoring
+            .iter()
+            .filter(|(_, card)| self.has_suit(card, Suit::Clubs));
+        let Some((club, _)) = clubs.next() else {
+            return false;
+        };
+        scoring.iter().any(|(index, card)| {
+            index != club
+                && [Suit::Spades, Suit::Hearts, Suit::Diamonds]
+                    .into_iter()
+                    .any(|suit| self.has_suit(card, suit))
+        })
+    }
+
+    /// The poker hand as currently levelled, or at level 1 if it is a secret
+    /// hand that has not been unlocked yet. `None` if the game left it out.
+    fn poker_hand(&self, kind: PokerHandKind) -> Option<Cow<'_, CurrentPokerHand>> {
+        match self.poker_hands.get(kind) {
+            Some(current) => Some(Cow::Borrowed(current)),
+            None => CurrentPokerHand::secret(kind).map(Cow::Owned),
+        }
+    }
+
+    /// The boss, if it refuses the hand outright so it scores nothing.
+    fn refused_by(&self, current: &CurrentPokerHand, played: usize) -> Option<Boss> {
+        let boss = self.boss?;
+        let refused = match boss {
+            Boss::TheEye => current.played_round > 0,
+            Boss::TheMouth => self
+                .poker_hands
+                .iter()
+                .any(|hand| hand.played_round > 0 && hand.hand.kind != current.hand.kind),
+            Boss::ThePsychic => played < 5,
+            _ => false,
+        };
+        refused.then_some(boss)
+    }
+
+    fn debuffed(&self, card: &PlayingCard) -> bool {
+        card.debuffed
+            || match self.boss {
+                Some(Boss::TheClub) => self.has_suit(card, Suit::Clubs),
+                Some(Boss::TheGoad) => self.has_suit(card, Suit::Spades),
+                Some(Boss::TheWindow) => self.has_suit(card, Suit::Diamonds),
+                Some(Boss::TheHead) => self.has_suit(card, Suit::Hearts),
+                Some(Boss::ThePlant) => self.is_face(card),
+                _ => false,
+            }
+    }
+
+    fn has_suit(&self, card: &PlayingCard, suit: Suit) -> bool {
+        self.rules.has_suit(card, suit)
+    }
+
+    fn is_face(&self, card: &PlayingCard) -> bool {
+        rank(card).is_some_and(|rank| {
+            self.has(JokerKind::Pareidolia) || matches!(rank, Rank::Jack | Rank::Queen | Rank::King)
+        })
+    }
+
+    fn has(&self, kind: JokerKind) -> bool {
+        self.effective().any(|(_, effective)| effective == kind)
+    }
+
+    /// Every Joker that has an effect, with the kind it acts as.
+    fn effective(&self) -> impl Iterator<Item = (usize, JokerKind)> + '_ {
+        (0..self.jokers.len()).filter_map(|index| Some((index, self.resolve(index)?)))
+    }
+
+    /// The kind the Joker at `index` acts as, following Blueprint and
+    /// Brainstorm to the Joker they copy.
+    fn resolve(&self, mut index: usize) -> Option<JokerKind> {
+        for _ in 0..self.jokers.len() {
+            match self.jokers.get(index)?.kind {
+                JokerKind::Blueprint => index += 1,
+                JokerKind::Brainstorm => index = 0,
+                kind => return Some(kind),
+            }
+        }
+        None
+    }
+}
+
+/// A predicted score, with how it was reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The poker hand the played cards make.
+    pub poker_hand: PokerHandKind,
+    /// Indices into the hand of the cards that scored.
+    pub scoring: Vec<usize>,
+    pub chips: f64,
+    pub mult: f64,
+    /// Every step that changed chips or mult, in the order the game applies them.
+    pub steps: Vec<Step>,
+}
+
+impl Score {
+    /// Chips times mult, as the game rounds it.
+    pub fn total(&self) -> f64 {
+        (self.chips * self.mult).floor()
+    }
+}
+
+/// One step of scoring, with the chips and mult after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub source: Source,
+    pub effect: Effect,
+    pub chips: f64,
+    pub mult: f64,
+}
+
+/// What caused a [`Step`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    PokerHand(PokerHandKind),
+    /// A card, by its index in the hand.
+    Card(usize),
+    /// A Joker, by its index in the Joker slots.
+    Joker(usize),
+    Boss(Boss),
+    Deck(Deck),
+}
+
+/// What a [`Step`] did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+    /// Chips and mult of the poker hand at its current level.
+    Base,
+    Chips(f64),
+    Mult(f64),
+    XMult(f64),
+    /// The next card trigger is a retrigger.
+    Retrigger,
+    /// The card is debuffed and scores nothing.
+    Debuffed,
+    /// The boss refuses the hand, so it scores nothing.
+    NotAllowed,
+    /// The poker hand is a level lower for this hand.
+    LevelDown,
+    /// Base chips and mult are halved.
+    Halve,
+    /// Chips and mult are averaged.
+    Balance,
+}
+
+struct Round {
+    steps: Vec<Step>,
+    chips: f64,
+    mult: f64,
+}
+
+impl Round {
+    fn chips(&mut self, source: Source, chips: f64) {
+        self.chips += chips;
+        self.push(source, Effect::Chips(chips));
+    }
+
+    fn mult(&mut self, source: Source, mult: f64) {
+        self.mult += mult;
+        self.push(source, Effect::Mult(mult));
+    }
+
+    fn xmult(&mut self, source: Source, xmult: f64) {
+        self.mult *= xmult;
+        self.push(source, Effect::XMult(xmult));
+    }
+
+    fn set(&mut self, source: Source, effect: Effect, chips: f64, mult: f64) {
+        self.chips = chips;
+        self.mult = mult;
+        self.push(source, effect);
+    }
+
+    fn push(&mut self, source: Source, effect: Effect) {
+        self.steps.push(Step {
+            source,
+            effect,
+            chips: self.chips,
+            mult: self.mult,
+        });
+    }
+
+    fn finish(self, poker_hand: PokerHandKind, scoring: &[(usize, &PlayingCard)]) -> Score {
+        Score {
+            poker_hand,
+            scoring: scoring.iter().map(|(index, _)| *index).collect(),
+            chips: self.chips,
+            mult: self.mult,
+            steps: self.steps,
+        }
+    }
+}
+
+/// The rank of a card, or `None` for Stone Cards, which have none.
+fn rank(card: &PlayingCard) -> Option<Rank> {
+    match card.enhancement {
+        Some(Enhancement::Stone) => None,
+        _ => Some(card.rank),
+    }
+}
+
+/// Chips a card of `rank` scores on its own.
+fn nominal(rank: Rank) -> f64 {
+    match rank {
+        Rank::Two => 2.0,
+        Rank::Three => 3.0,
+        Rank::Four => 4.0,
+        Rank::Five => 5.0,
+        Rank::Six => 6.0,
+        Rank::Seven => 7.0,
+        Rank::Eight => 8.0,
+        Rank::Nine => 9.0,
+        Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => 10.0,
+        Rank::Ace => 11.0,
+    }
+}
+
+/// Chips and mult a poker hand gains per level.
+fn per_level(kind: PokerHandKind) -> (u64, u64) {
+    match kind {
+        PokerHandKind::HighCard => (10, 1),
+        PokerHandKind::Pair => (15, 1),
+        PokerHandKind::TwoPair => (20, 1),
+        PokerHandKind::ThreeOfAKind => (20, 2),
+        PokerHandKind::Straight => (30, 3),
+        PokerHandKind::Flush => (15, 2),
+        PokerHandKind::FullHouse => (25, 2),
+        PokerHandKind::FourOfAKind => (30, 3),
+        PokerHandKind::StraightFlush => (40, 4),
+        PokerHandKind::FiveOfAKind => (35, 3),
+        PokerHandKind::FlushHouse => (40, 4),
+        PokerHandKind::FlushFive => (50, 3),
+    }
+}
@@ -0,0 +1,207 @@
+//! Accepting many games at once, e.g. to evaluate a bot on a farm of game
+//! instances.
+
+use crate::balatro::Balatro;
+use crate::net::{Error, Peer, Secret, Socket};
+
+use log::{info, warn};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::Stream;
+
+/// Pause after a failed accept on the listener itself, so a broken socket does
+/// not spin the accept loop.
+pub(crate) const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Identifies one game connection accepted by a [`Server`]. Ids are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GameId(u64);
+
+impl std::fmt::Display for GameId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "game-{}", self.0)
+    }
+}
+
+/// What the [`Registry`] knows about a connected game.
+#[derive(Debug, Clone)]
+pub struct GameInfo {
+    pub id: GameId,
+    pub peer: Option<Peer>,
+    pub connected_at: SystemTime,
+}
+
+/// The games currently connected to a [`Server`]. Games are added once they
+/// finish the handshake, and removed as soon as their connection ends.
+///
+/// Cloning gives another handle to the same registry.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    games: Arc<Mutex<HashMap<GameId, GameInfo>>>,
+}
+
+impl Registry {
+    /// Every live game, ordered by id.
+    pub fn games(&self) -> Vec<GameInfo> {
+        let mut games: Vec<_> = self.lock().values().cloned().collect();
+        games.sort_by_key(|game| game.id);
+        games
+    }
+
+    pub fn get(&self, id: GameId) -> Option<GameInfo> {
+        self.lock().get(&id).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<GameId, GameInfo>> {
+        // The map is never left half updated, so a poisoned lock is still usable.
+        self.games.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Accepts games continuously in the background, created by [`Remotro::serve`](crate::Remotro::serve).
+///
+/// Each connection is set up in its own task, so a slow or misbehaving game
+/// does not hold up the others. Newly connected games are handed out as a
+/// [`Stream`] of `(GameId, Balatro)`, and each [`Balatro`] can be moved into
+/// its own task. Dropping the server stops accepting, but leaves games that
+/// were already handed out connected.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), remotro::net::Error> {
+/// use tokio_stream::StreamExt;
+///
+/// let mut server = remotro::Remotro::host("127.0.0.1", 34143).await?.serve();
+/// while let Some((id, mut balatro)) = server.next().await {
+///     tokio::spawn(async move {
+///         let _screen = balatro.screen().await;
+///         println!("{id} is done");
+///     });
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Server {
+    incoming: mpsc::Receiver<(GameId, Balatro)>,
+    registry: Registry,
+    local_addr: Option<SocketAddr>,
+    accept_task: JoinHandle<()>,
+}
+
+impl Server {
+    pub(crate) fn new(socket: Socket, secret: Option<Secret>) -> Self {
+        let (tx_incoming, incoming) = mpsc::channel(socket.config().channel_buffer_size.get());
+        let registry = Registry::default();
+        let local_addr = socket.local_addr();
+        let accept_task = tokio::spawn(run_accept(socket, secret, registry.clone(), tx_incoming));
+        Self {
+            incoming,
+            registry,
+            local_addr,
+            accept_task,
+        }
+    }
+
+    /// The games currently connected.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// The TCP address games connect to, or `None` for a Unix domain socket.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+}
+
+impl Stream for Server {
+    type Item = (GameId, Balatro);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.incoming.poll_recv(cx)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+async fn run_accept(
+    mut socket: Socket,
+    secret: Option<Secret>,
+    registry: Registry,
+    tx_incoming: mpsc::Sender<(GameId, Balatro)>,
+) {
+    let next_id = Arc::new(AtomicU64::new(0));
+    loop {
+        let incoming = match socket.accept().await {
+            Ok(incoming) => incoming,
+            Err(Error::Timeout) => continue,
+            Err(e) => {
+                warn!("Failed to accept connection: {e}");
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+                continue;
+            }
+        };
+        if tx_incoming.is_closed() {
+            break;
+        }
+        let (secret, registry, tx_incoming, next_id) = (
+            secret.clone(),
+            registry.clone(),
+            tx_incoming.clone(),
+            next_id.clone(),
+        );
+        tokio::spawn(async move {
+            let connection = match incoming.upgrade().await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("Failed to upgrade game connection: {e}");
+                    return;
+                }
+            };
+            let balatro = match crate::establish(connection, secret.as_ref(), false).await {
+                Ok(balatro) => balatro,
+                Err(e) => {
+                    warn!("Failed to set up game connection: {e}");
+                    return;
+                }
+            };
+            let id = GameId(next_id.fetch_add(1, Ordering::Relaxed));
+            info!("Accepted {id}");
+            registry.lock().insert(
+                id,
+                GameInfo {
+                    id,
+                    peer: balatro.peer().cloned(),
+                    connected_at: SystemTime::now(),
+                },
+            );
+            let closed = balatro.closed();
+            if tx_incoming.send((id, balatro)).await.is_err() {
+                registry.lock().remove(&id);
+                return;
+            }
+            closed.await;
+            info!("{id} disconnected");
+            registry.lock().remove(&id);
+        });
+    }
+}
@@ -0,0 +1,105 @@
+use crate::Remotro;
+use crate::balatro::Balatro;
+use crate::net::Error;
+use crate::server::ACCEPT_BACKOFF;
+
+use log::{info, warn};
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+use tokio::time::Instant;
+
+/// A [`Balatro`] that survives the game reconnecting.
+///
+/// The game reports a session token in the hello exchange, which stays the same
+/// for as long as the game is running. When the connection drops, e.g. with
+/// [`Error::ConnectionClosed`], [`Session::resume`] waits for a game with that
+/// token to connect again and rebinds to it, so the bot can carry on calling
+/// [`Balatro::screen`] with the state it already built up.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), remotro::net::Error> {
+/// use remotro::{Remotro, Session, balatro::Error};
+///
+/// let mut remotro = Remotro::host("127.0.0.1", 34143).await?;
+/// let mut session = Session::start(&mut remotro).await?;
+/// loop {
+///     match session.screen().await {
+///         Ok(screen) => { /* play */ }
+///         Err(Error::Net(_)) => session.resume(&mut remotro).await?,
+///         Err(e) => println!("{e}"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct Session {
+    balatro: Balatro,
+    token: String,
+}
+
+impl Session {
+    /// Accepts a game and starts a session with it. Fails if the game does not
+    /// report a session token.
+    pub async fn start(remotro: &mut Remotro) -> Result<Self, Error> {
+        let balatro = remotro.accept().await?;
+        let token = balatro
+            .peer()
+            .and_then(|peer| peer.session.clone())
+            .ok_or(Error::Message(Cow::Borrowed(
+                "Game did not report a session token",
+            )))?;
+        info!("Started session {token}");
+        Ok(Self { balatro, token })
+    }
+
+    /// The token of the game instance this session is bound to.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Waits for the game of this session to reconnect, then rebinds to the new
+    /// connection. Other games connecting in the meantime are turned away.
+    ///
+    /// Fails with [`Error::Timeout`] if the game does not come back within the
+    /// [`session_grace`](crate::net::ConnectionConfig::session_grace) of `remotro`,
+    /// counted from this call.
+    pub async fn resume(&mut self, remotro: &mut Remotro) -> Result<(), Error> {
+        let deadline = Instant::now() + remotro.config().session_grace;
+        info!("Waiting for session {} to reconnect", self.token);
+        loop {
+            let balatro = match tokio::time::timeout_at(deadline, remotro.accept()).await {
+                Err(_) => {
+                    warn!("Session {} was not resumed in time", self.token);
+                    return Err(Error::Timeout);
+                }
+                Ok(Err(e)) => {
+                    warn!("Failed to accept game while resuming: {e}");
+                    tokio::time::sleep_until(deadline.min(Instant::now() + ACCEPT_BACKOFF)).await;
+                    continue;
+                }
+                Ok(Ok(balatro)) => balatro,
+            };
+            match balatro.peer().and_then(|peer| peer.session.as_deref()) {
+                Some(token) if token == self.token => {
+                    info!("Resumed session {token}");
+                    self.balatro = balatro;
+                    return Ok(());
+                }
+                token => warn!("Turning away game with session {token:?} while resuming"),
+            }
+        }
+    }
+}
+
+impl Deref for Session {
+    type Target = Balatro;
+
+    fn deref(&self) -> &Balatro {
+        &self.balatro
+    }
+}
+
+impl DerefMut for Session {
+    fn deref_mut(&mut self) -> &mut Balatro {
+        &mut self.balatro
+    }
+}
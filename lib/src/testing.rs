@@ -0,0 +1,546 @@
+//! A scriptable stand-in for the game side of the protocol, for testing
+//! clients without running Balatro. Requires the `testing` feature.
+//!
+//! A [`FakeGame`] connects to a [`Remotro`] like the mod does,
+//! answers heartbeats and the hello exchange on its own, and answers every
+//! other request from a script, keyed by request kind. Scripting `hello`
+//! replaces the built-in answer, e.g. to report another protocol version.
+//!
+//! If the client asks for state epochs, the game stamps every answer with one
+//! and moves it on after each successful action. Actions that echo an older
+//! epoch are refused with [`GameError::StaleScreen`], and
+//! [`GameHandle::advance`] moves the game on as if the player had acted in it.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), remotro::net::Error> {
+//! use remotro::{Remotro, balatro::CurrentScreen, testing::{FakeGame, fixtures}};
+//!
+//! let (mut balatro, game) = FakeGame::new()
+//!     .screen(serde_json::json!({ "Menu": fixtures::menu() }))
+//!     .accepted(Remotro::builder())
+//!     .await?;
+//!
+//! assert!(matches!(balatro.screen().await, Ok(CurrentScreen::Menu(_))));
+//!
+//! drop(balatro);
+//! let requests = game.finish().await;
+//! assert_eq!(requests[0].kind, "screen/get");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::balatro::{Balatro, GameError};
+use crate::net::{
+    ConnectionConfig, Error, PROTOCOL_VERSION, StreamTransport, Transport, hello, protocol,
+    transcript::{Direction, Record},
+};
+use crate::{Remotro, RemotroBuilder};
+
+use hmac::{Hmac, Mac};
+use log::{debug, warn};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::time::Duration;
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// A request the fake game received from the client.
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub kind: String,
+    pub body: Value,
+}
+
+/// Script for the game side of one connection. See the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct FakeGame {
+    /// Response packets per request kind, used in order. The last one repeats.
+    responses: HashMap<String, VecDeque<String>>,
+    session: Option<String>,
+    secret: Option<Vec<u8>>,
+    /// Ignores the hello exchange, like versions of the mod from before it.
+    legacy: bool,
+    /// How long to hold back the next answer per request kind.
+    delays: HashMap<String, Duration>,
+    /// The current state epoch, once the client has asked for them.
+    epoch: Option<u64>,
+}
+
+impl FakeGame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers the next `request` with the raw `packet`, in `kind!body` form.
+    /// Scripting the same request again queues another answer; the last
+    /// answer is repeated once the queue runs out.
+    pub fn respond(mut self, request: impl Into<String>, packet: impl Into<String>) -> Self {
+        self.responses
+            .entry(request.into())
+            .or_default()
+            .push_back(packet.into());
+        self
+    }
+
+    /// Answers the next `request` successfully with `body`, as a
+    /// `result/<response>` packet.
+    pub fn ok(self, request: impl Into<String>, response: &str, body: Value) -> Self {
+        self.respond(
+            request,
+            format!("result/{response}!{}", json!({ "Ok": body })),
+        )
+    }
+
+    /// Answers `screen/get` with `screen`, e.g. `json!({ "Menu": fixtures::menu() })`.
+    pub fn screen(self, screen: Value) -> Self {
+        self.ok("screen/get", "screen/current", screen)
+    }
+
+    /// Answers the next `request` with a game error.
+    pub fn err(self, request: impl Into<String>, response: &str, error: GameError) -> Self {
+        self.respond(
+            request,
+            format!("result/{response}!{}", json!({ "Err": error })),
+        )
+    }
+
+    /// Holds back the next answer to `request` by `after`, like a game busy
+    /// with an animation. Nothing else is answered in the meantime.
+    pub fn late(mut self, request: impl Into<String>, after: Duration) -> Self {
+        self.delays.insert(request.into(), after);
+        self
+    }
+
+    /// Reports `token` as the session token in the hello exchange.
+    pub fn session(mut self, token: impl Into<String>) -> Self {
+        self.session = Some(token.into());
+        self
+    }
+
+    /// Answers authentication challenges with `secret`, for clients built with
+    /// [`RemotroBuilder::secret`].
+    pub fn secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Ignores the hello exchange, like versions of the mod from before it
+    /// existed. The client waits out its hello timeout before going on.
+    pub fn legacy(mut self) -> Self {
+        self.legacy = true;
+        self
+    }
+
+    /// Scripts every request in a transcript written by a
+    /// [`Recorder`](crate::net::transcript::Recorder) with the response it got,
+    /// so a recorded session can be replayed without the game.
+    pub fn from_transcript(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let records = std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Record>, _>>()?;
+
+        let mut game = Self::new();
+        for (i, request) in records.iter().enumerate() {
+            if request.direction != Direction::Sent || request.kind == "hello" {
+                continue;
+            }
+            let response = records[i + 1..].iter().find(|record| {
+                record.direction == Direction::Received
+                    && record.connection == request.connection
+                    && match request.id {
+                        Some(id) => record.id == Some(id),
+                        None => record.kind.starts_with("result/"),
+                    }
+            });
+            match response {
+                Some(response) => {
+                    let packet = format!("{}!{}", response.kind, response.body);
+                    game = game.respond(request.kind.clone(), packet);
+                }
+                None => warn!("Transcript has no response to {}", request.kind),
+            }
+        }
+        Ok(game)
+    }
+
+    /// Connects to a [`Remotro`] listening on `addr`, with the
+    /// same framing and heartbeat as the mod, and starts playing the script.
+    pub async fn connect(self, addr: impl ToSocketAddrs) -> Result<GameHandle, Error> {
+        let stream = TcpStream::connect(addr).await?;
+        Ok(self.spawn(StreamTransport::new(stream, &ConnectionConfig::default())))
+    }
+
+    /// Hosts a [`Remotro`] built by `builder` on a free loopback port and
+    /// connects to it, leaving the game to be accepted.
+    pub async fn host(self, builder: RemotroBuilder) -> Result<(Remotro, GameHandle), Error> {
+        let remotro = builder.host("127.0.0.1", 0).await?;
+        let addr = remotro.local_addr().expect("TCP sockets have an address");
+        Ok((remotro, self.connect(addr).await?))
+    }
+
+    /// Like [`host`](Self::host), and accepts the game too.
+    pub async fn accepted(self, builder: RemotroBuilder) -> Result<(Balatro, GameHandle), Error> {
+        let (mut remotro, game) = self.host(builder).await?;
+        Ok((remotro.accept().await?, game))
+    }
+
+    /// Plays the script over any transport, e.g. one end of a
+    /// [`duplex`](crate::net::duplex).
+    pub fn spawn(self, transport: impl Transport + 'static) -> GameHandle {
+        let (control, rx_control) = mpsc::channel(32);
+        GameHandle {
+            control,
+            task: tokio::spawn(self.run(Box::new(transport), rx_control)),
+        }
+    }
+
+    async fn run(
+        mut self,
+        mut transport: Box<dyn Transport>,
+        mut rx_control: mpsc::Receiver<Control>,
+    ) -> Vec<ReceivedRequest> {
+        let mut received = Vec::new();
+        loop {
+            let packet = tokio::select! {
+                packet = transport.recv() => match packet {
+                    Ok(packet) => packet,
+                    Err(_) => break,
+                },
+                Some(control) = rx_control.recv() => {
+                    match control {
+                        Control::Push(packet) => {
+                            if transport.send(packet).await.is_err() {
+                                break;
+                            }
+                        }
+                        Control::Advance(done) => {
+                            if let Some(epoch) = &mut self.epoch {
+                                *epoch += 1;
+                            }
+                            let _ = done.send(());
+                        }
+                    }
+                    continue;
+                }
+            };
+            let (id, packet) = protocol::untag(packet);
+            let (kind, body) = packet.split_once('!').unwrap_or((&packet, ""));
+            let mut body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+            debug!("Fake game received {kind}");
+
+            let reply = match kind {
+                "hello" if self.legacy => None,
+                "hello" => self
+                    .next_response("hello")
+                    .or_else(|| Some(self.hello(&body))),
+                "auth/challenge" => self.auth(&body),
+                _ => {
+                    let echoed = body
+                        .as_object_mut()
+                        .and_then(|body| body.remove("epoch"))
+                        .and_then(|epoch| epoch.as_u64());
+                    let reply = match (echoed, self.epoch) {
+                        (Some(echoed), Some(epoch)) if echoed != epoch => self.stale(kind),
+                        _ => self.next_response(kind),
+                    };
+                    received.push(ReceivedRequest {
+                        kind: kind.to_string(),
+                        body,
+                    });
+                    reply.map(|reply| self.stamp(kind, reply))
+                }
+            };
+            let Some(reply) = reply else {
+                warn!("Fake game has no response scripted for {kind}");
+                continue;
+            };
+            let reply = match id {
+                Some(id) => protocol::tag(&reply, id),
+                None => reply,
+            };
+            if let Some(after) = self.delays.remove(kind) {
+                tokio::time::sleep(after).await;
+            }
+            if transport.send(reply).await.is_err() {
+                break;
+            }
+        }
+        received
+    }
+
+    fn next_response(&mut self, kind: &str) -> Option<String> {
+        let queue = self.responses.get_mut(kind)?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+
+    /// Refuses `request` as stale, with the response kind scripted for it.
+    fn stale(&self, request: &str) -> Option<String> {
+        let scripted = self.responses.get(request)?.front()?;
+        let (kind, _) = scripted.split_once('!')?;
+        Some(format!(
+            "{kind}!{}",
+            json!({ "Err": GameError::StaleScreen })
+        ))
+    }
+
+    /// Adds the state epoch to an answer to `request`, moving it on first if
+    /// the answer is a successful action.
+    fn stamp(&mut self, request: &str, reply: String) -> String {
+        let Some(epoch) = &mut self.epoch else {
+            return reply;
+        };
+        let Some((kind, body)) = reply.split_once('!') else {
+            return reply;
+        };
+        let Ok(Value::Object(mut body)) = serde_json::from_str(body) else {
+            return reply;
+        };
+        if request != "screen/get" && body.contains_key("Ok") {
+            *epoch += 1;
+        }
+        body.insert("epoch".to_string(), json!(*epoch));
+        format!("{kind}!{}", Value::Object(body))
+    }
+
+    fn hello(&mut self, body: &Value) -> String {
+        let epochs = body["capabilities"]
+            .as_array()
+            .is_some_and(|caps| caps.iter().any(|c| c == hello::STATE_EPOCHS));
+        let mut capabilities = vec![hello::REQUEST_IDS];
+        if epochs {
+            self.epoch = Some(0);
+            capabilities.push(hello::STATE_EPOCHS);
+        }
+        let mut reply = json!({
+            "version": PROTOCOL_VERSION,
+            "capabilities": capabilities,
+        });
+        if let Some(session) = &self.session {
+            reply["session"] = json!(session);
+        }
+        format!("result/hello!{}", json!({ "Ok": reply }))
+    }
+
+    fn auth(&self, body: &Value) -> Option<String> {
+        let secret = self.secret.as_ref()?;
+        let nonce = hex::decode(body["nonce"].as_str()?).ok()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).ok()?;
+        mac.update(&nonce);
+        let mac = hex::encode(mac.finalize().into_bytes());
+        Some(format!(
+            "result/auth/response!{}",
+            json!({ "Ok": { "mac": mac } })
+        ))
+    }
+}
+
+/// A running [`FakeGame`]. The game keeps running until the client hangs up.
+pub struct GameHandle {
+    control: mpsc::Sender<Control>,
+    task: JoinHandle<Vec<ReceivedRequest>>,
+}
+
+enum Control {
+    Push(String),
+    Advance(oneshot::Sender<()>),
+}
+
+impl GameHandle {
+    /// Sends a packet the client did not ask for, e.g. an `event/*` packet.
+    pub async fn push(&self, packet: impl Into<String>) {
+        let _ = self.control.send(Control::Push(packet.into())).await;
+    }
+
+    /// Moves the state epoch on, as if the player had acted in the game, so
+    /// screens the client already holds become stale. Returns once the game
+    /// has moved on.
+    pub async fn advance(&self) {
+        let (done, moved) = oneshot::channel();
+        if self.control.send(Control::Advance(done)).await.is_ok() {
+            let _ = moved.await;
+        }
+    }
+
+    /// Hangs up on the client, like the game closing or losing its network.
+    pub fn disconnect(self) {
+        self.task.abort();
+    }
+
+    /// Waits for the client to hang up, and returns every scripted request the
+    /// game received, in order. The hello exchange and authentication are not
+    /// included.
+    pub async fn finish(self) -> Vec<ReceivedRequest> {
+        self.task.await.unwrap_or_default()
+    }
+}
+
+/// Bodies for common screens, early in a White Stake run on the Red Deck.
+pub mod fixtures {
+    use crate::balatro::{
+        blinds::{
+            BigBlindChoice, BlindState, Boss, BossBlindChoice, CurrentBlind, SmallBlindChoice, Tag,
+        },
+        boosters::BoosterPackKind,
+        deck::{PlayingCard, Rank, Suit},
+        hud::{CurrentBlinds, CurrentPokerHand, CurrentPokerHands, RunInfo, protocol::HudInfo},
+        jokers::{Joker, JokerKind},
+        menu::{Deck, Stake},
+        play::{HandCard, PokerHand, PokerHandKind, protocol::PlayInfo},
+        shop::{BoosterPack, MainCard, protocol::ShopInfo},
+    };
+    use serde_json::{Value, json};
+
+    fn poker_hand(kind: PokerHandKind, chips: u64, mult: u64) -> CurrentPokerHand {
+        CurrentPokerHand {
+            hand: PokerHand {
+                kind,
+                level: 1,
+                chips,
+                mult,
+            },
+            played: 0,
+            played_round: 0,
+        }
+    }
+
+    fn joker(kind: JokerKind, price: u64) -> Joker {
+        Joker {
+            price,
+            ..Joker::new(kind)
+        }
+    }
+
+    fn hud_info() -> HudInfo {
+        let mut deck = Vec::new();
+        for suit in [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
+            let mut rank = Rank::Two;
+            for _ in 0..13 {
+                deck.push(PlayingCard::new(rank, suit));
+                rank = rank.next();
+            }
+        }
+        HudInfo {
+            hands: 4,
+            discards: 3,
+            round: 1,
+            ante: 1,
+            money: 4,
+            joker_slots: 5,
+            jokers: vec![joker(JokerKind::Joker, 2)],
+            tags: Vec::new(),
+            consumable_slots: 2,
+            consumables: Vec::new(),
+            run_info: RunInfo {
+                poker_hands: CurrentPokerHands {
+                    high_card: poker_hand(PokerHandKind::HighCard, 5, 1),
+                    pair: poker_hand(PokerHandKind::Pair, 10, 2),
+                    two_pair: poker_hand(PokerHandKind::TwoPair, 20, 2),
+                    three_of_a_kind: poker_hand(PokerHandKind::ThreeOfAKind, 30, 3),
+                    straight: poker_hand(PokerHandKind::Straight, 30, 4),
+                    flush: poker_hand(PokerHandKind::Flush, 35, 4),
+                    full_house: poker_hand(PokerHandKind::FullHouse, 40, 4),
+                    four_of_a_kind: poker_hand(PokerHandKind::FourOfAKind, 60, 7),
+                    straight_flush: poker_hand(PokerHandKind::StraightFlush, 100, 8),
+                    five_of_a_kind: None,
+                    flush_house: None,
+                    flush_fives: None,
+                },
+                blinds: CurrentBlinds {
+                    small: SmallBlindChoice {
+                        state: BlindState::Current,
+                        chips: 300.0,
+                        tag: Tag::Investment,
+                    },
+                    big: BigBlindChoice {
+                        state: BlindState::Upcoming,
+                        chips: 450.0,
+                        tag: Tag::Charm,
+                    },
+                    boss: BossBlindChoice {
+                        kind: Boss::TheHook,
+                        state: BlindState::Upcoming,
+                        chips: 600.0,
+                    },
+                },
+                vouchers_redeemed: Vec::new(),
+                stake: Stake::White,
+                deck: Deck::Red,
+            },
+            deck,
+        }
+    }
+
+    /// The HUD shared by every in-run screen: one Joker and a full 52 card deck.
+    pub fn hud() -> Value {
+        serde_json::to_value(hud_info()).expect("HUD serializes")
+    }
+
+    /// The main menu, without a saved run.
+    pub fn menu() -> Value {
+        json!({ "saved_run": null })
+    }
+
+    /// The small blind, with a hand of eight cards and nothing selected.
+    pub fn play() -> Value {
+        let hand = [
+            (Rank::Ace, Suit::Spades),
+            (Rank::Ace, Suit::Hearts),
+            (Rank::King, Suit::Clubs),
+            (Rank::Nine, Suit::Diamonds),
+            (Rank::Seven, Suit::Spades),
+            (Rank::Five, Suit::Hearts),
+            (Rank::Four, Suit::Clubs),
+            (Rank::Two, Suit::Diamonds),
+        ];
+        let info = PlayInfo {
+            current_blind: CurrentBlind::Small { chips: 300 },
+            hand: hand
+                .into_iter()
+                .map(|(rank, suit)| HandCard {
+                    card: Some(PlayingCard::new(rank, suit)),
+                    selected: false,
+                })
+                .collect(),
+            score: 0.0,
+            hand_size: 8,
+            hud: hud_info(),
+            poker_hand: None,
+            discarded: Vec::new(),
+        };
+        serde_json::to_value(info).expect("play info serializes")
+    }
+
+    /// A shop with one Joker for sale and a Buffoon Pack.
+    pub fn shop() -> Value {
+        let info = ShopInfo {
+            hud: hud_info(),
+            main: vec![MainCard::Joker(joker(JokerKind::GreedyJoker, 5))],
+            vouchers: Vec::new(),
+            boosters: vec![BoosterPack {
+                kind: BoosterPackKind::BuffoonNormal,
+                price: 4,
+            }],
+        };
+        serde_json::to_value(info).expect("shop info serializes")
+    }
+
+    /// An opened Buffoon Pack offering two Jokers, for answering
+    /// `shop/buybooster` with `{"Buffoon": buffoon_pack()}`.
+    pub fn buffoon_pack() -> Value {
+        json!({
+            "hud": hud(),
+            "booster": BoosterPackKind::BuffoonNormal,
+            "options": [joker(JokerKind::Banner, 5), joker(JokerKind::Misprint, 4)],
+            "selections_left": 1,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+//! The pre-shared key handshake on accept.
+#![cfg(feature = "testing")]
+
+use remotro::{Remotro, net, testing::FakeGame};
+use std::time::Duration;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+#[tokio::test]
+async fn authenticated_games_are_accepted() {
+    let result = FakeGame::new()
+        .secret("hunter2")
+        .accepted(Remotro::builder().secret("hunter2"))
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn wrong_secret_is_unauthorized() {
+    let (mut remotro, _game) = FakeGame::new()
+        .secret("password")
+        .host(Remotro::builder().secret("hunter2"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        remotro.accept().await,
+        Err(net::Error::Unauthorized)
+    ));
+}
+
+#[tokio::test]
+async fn missing_secret_is_unauthorized() {
+    let builder = Remotro::builder()
+        .secret("hunter2")
+        .handshake_timeout(Duration::from_millis(200));
+    let (mut remotro, _game) = FakeGame::new().host(builder).await.unwrap();
+    assert!(matches!(
+        remotro.accept().await,
+        Err(net::Error::Unauthorized)
+    ));
+}
+
+#[tokio::test]
+async fn hello_waits_for_authentication() {
+    let mut remotro = Remotro::builder()
+        .secret("hunter2")
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let peer = tokio::spawn(async move {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut seen = vec![lines.next_line().await.unwrap().unwrap()];
+        writer
+            .write_all(b"result/auth/response!{\"Ok\":{\"mac\":\"00\"}}\n")
+            .await
+            .unwrap();
+        while let Ok(Some(line)) = lines.next_line().await {
+            seen.push(line);
+        }
+        seen
+    });
+    assert!(matches!(
+        remotro.accept().await,
+        Err(net::Error::Unauthorized)
+    ));
+    let seen = peer.await.unwrap();
+    assert!(seen[0].starts_with("auth/challenge!"));
+    assert!(seen.iter().all(|line| !line.starts_with("hello")));
+}
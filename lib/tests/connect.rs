@@ -0,0 +1,47 @@
+//! Dialing a game that listens, instead of waiting for it to connect.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::CurrentScreen,
+    net::{ConnectionConfig, PROTOCOL_VERSION, StreamTransport},
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn connect_to_a_listening_game() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let game = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        FakeGame::new()
+            .session("abc")
+            .screen(json!({ "Menu": fixtures::menu() }))
+            .spawn(StreamTransport::new(stream, &ConnectionConfig::default()))
+    });
+
+    let mut balatro = Remotro::builder()
+        .inactivity_timeout(Duration::from_millis(50))
+        .ping_response_timeout(Duration::from_millis(200))
+        .max_ping_retries(2)
+        .connect_timeout(Duration::from_secs(1))
+        .connect(addr)
+        .await
+        .unwrap();
+    let peer = balatro.peer().unwrap();
+    assert_eq!(peer.version.to_string(), PROTOCOL_VERSION);
+    assert_eq!(peer.session.as_deref(), Some("abc"));
+
+    // Without pongs, the connection would time out after about 450ms of this.
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    assert!(!balatro.is_closed());
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.await.unwrap().finish().await;
+    assert_eq!(requests.len(), 1);
+}
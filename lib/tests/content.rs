@@ -0,0 +1,62 @@
+//! Content lookups from the generated tables.
+
+use remotro::balatro::{
+    blinds::{Boss, Tag},
+    consumables::{Consumable, PlanetCard, PlanetKind, SpectralKind, TarotKind},
+    content::Rarity,
+    deck::Suit,
+    jokers::JokerKind,
+    shop::VoucherKind,
+};
+
+#[test]
+fn jokers_with_fields_share_their_content() {
+    let info = JokerKind::Ancient { suit: Suit::Hearts }.info();
+    assert_eq!(info.name, "Ancient Joker");
+    assert_eq!(info.rarity, Rarity::Rare);
+    assert_eq!(JokerKind::Caino { xmult: 1.0 }.key(), "j_caino");
+    assert_eq!(
+        JokerKind::Caino { xmult: 3.0 }.info().rarity,
+        Rarity::Legendary
+    );
+    assert!(JokerKind::Joker.info().unlock.is_none());
+}
+
+#[test]
+fn consumables_vouchers_tags_and_bosses() {
+    assert_eq!(TarotKind::Heirophant.info().name, "The Hierophant");
+    assert_eq!(SpectralKind::TheSoul.info().cost, 4);
+    let planet = Consumable::Planet(PlanetCard {
+        kind: PlanetKind::PlanetX,
+        price: 3,
+        negative: false,
+    });
+    assert_eq!(planet.info().name, "Planet X");
+    assert!(VoucherKind::OverstockPlus.info().unlock.is_some());
+    assert_eq!(Tag::D6.info().name, "D6 Tag");
+    let vessel = Boss::VioletVessel.info();
+    assert!(vessel.showdown);
+    assert_eq!(vessel.size, 6.0);
+    assert!(!Boss::TheWall.info().showdown);
+}
+
+#[test]
+fn every_variant_has_content() {
+    for joker in JokerKind::iter() {
+        assert!(!joker.info().name.is_empty(), "{joker:?}");
+    }
+    let cards = TarotKind::iter()
+        .map(|tarot| tarot.info())
+        .chain(PlanetKind::iter().map(|planet| planet.info()))
+        .chain(SpectralKind::iter().map(|spectral| spectral.info()))
+        .chain(VoucherKind::iter().map(|voucher| voucher.info()));
+    for card in cards {
+        assert!(!card.effect.is_empty(), "{}", card.name);
+    }
+    for tag in Tag::iter() {
+        assert!(tag.info().min_ante >= 1, "{tag:?}");
+    }
+    for boss in Boss::iter() {
+        assert!(boss.info().size > 0.0, "{boss:?}");
+    }
+}
@@ -0,0 +1,148 @@
+//! Draw pile tracking and draw odds over a standard 52 card deck.
+
+use remotro::balatro::{
+    deck::{DeckTracker, Enhancement, PlayingCard, Rank, Suit},
+    play::{HandCard, HandRules},
+};
+
+fn deck() -> Vec<PlayingCard> {
+    let mut deck = Vec::new();
+    for suit in [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
+        let mut rank = Rank::Two;
+        for _ in 0..13 {
+            deck.push(PlayingCard::new(rank, suit));
+            rank = rank.next();
+        }
+    }
+    deck
+}
+
+fn hand(cards: &[PlayingCard]) -> Vec<HandCard> {
+    cards
+        .iter()
+        .map(|card| HandCard {
+            card: Some(card.clone()),
+            selected: false,
+        })
+        .collect()
+}
+
+/// Four Hearts and four Spades.
+fn opening_hand() -> Vec<PlayingCard> {
+    vec![
+        PlayingCard::new(Rank::Ace, Suit::Hearts),
+        PlayingCard::new(Rank::King, Suit::Hearts),
+        PlayingCard::new(Rank::Nine, Suit::Hearts),
+        PlayingCard::new(Rank::Four, Suit::Hearts),
+        PlayingCard::new(Rank::Ace, Suit::Spades),
+        PlayingCard::new(Rank::Queen, Suit::Spades),
+        PlayingCard::new(Rank::Seven, Suit::Spades),
+        PlayingCard::new(Rank::Two, Suit::Spades),
+    ]
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn draw_pile_excludes_the_hand() {
+    let mut tracker = DeckTracker::new();
+    tracker.observe_cards(1, &deck(), &hand(&opening_hand()), &[]);
+    assert_eq!(tracker.draw_pile().len(), 44);
+
+    // 9 Hearts are left among 44 cards.
+    let none = (35.0 * 34.0 * 33.0) / (44.0 * 43.0 * 42.0);
+    let odds = tracker.probability(3, 1, |card| card.suit == Suit::Hearts);
+    assert!(close(odds, 1.0 - none));
+    assert_eq!(tracker.probability(3, 0, |_| false), 1.0);
+    assert_eq!(tracker.probability(3, 4, |_| true), 0.0);
+}
+
+#[test]
+fn cards_leaving_the_hand_are_spent_until_the_next_round() {
+    let deck = deck();
+    let mut tracker = DeckTracker::new();
+    let mut cards = opening_hand();
+    tracker.observe_cards(1, &deck, &hand(&cards), &[]);
+
+    // Two Spades are played, and two Clubs drawn in their place.
+    cards.truncate(6);
+    cards.push(PlayingCard::new(Rank::Three, Suit::Clubs));
+    cards.push(PlayingCard::new(Rank::Five, Suit::Clubs));
+    tracker.observe_cards(1, &deck, &hand(&cards), &[]);
+    assert_eq!(tracker.spent().len(), 2);
+    assert_eq!(tracker.draw_pile().len(), 42);
+
+    // The game listing a spent card as discarded does not count it twice.
+    let discarded = [PlayingCard::new(Rank::Seven, Suit::Spades)];
+    tracker.observe_cards(1, &deck, &hand(&cards), &discarded);
+    assert_eq!(tracker.spent().len(), 2);
+
+    tracker.observe_cards(2, &deck, &hand(&opening_hand()), &[]);
+    assert!(tracker.spent().is_empty());
+    assert_eq!(tracker.draw_pile().len(), 44);
+}
+
+#[test]
+fn flush_odds_after_a_discard() {
+    let mut tracker = DeckTracker::new();
+    tracker.observe_cards(1, &deck(), &hand(&opening_hand()), &[]);
+
+    // Throwing the four Spades needs one Heart in four draws.
+    let odds = tracker
+        .flush_odds(&[4, 5, 6, 7], HandRules::default())
+        .unwrap();
+    let (suit, hearts) = odds[1];
+    assert_eq!(suit, Suit::Hearts);
+    let none = (35.0 * 34.0 * 33.0 * 32.0) / (44.0 * 43.0 * 42.0 * 41.0);
+    assert!(close(hearts, 1.0 - none));
+    // Clubs would need five from four draws.
+    assert_eq!(odds[2], (Suit::Clubs, 0.0));
+
+    let four_fingers = HandRules {
+        four_fingers: true,
+        ..HandRules::default()
+    };
+    // The Hearts and Spades are already four-card Flushes.
+    let expected = tracker.expected_flush_completions(&[], four_fingers);
+    assert!(close(expected.unwrap(), 2.0));
+
+    // Repeated indices count once; indices outside the hand are rejected.
+    let repeated = tracker.flush_odds(&[4, 4, 5, 6, 7, 7], HandRules::default());
+    assert_eq!(repeated, Some(odds));
+    assert_eq!(tracker.flush_odds(&[8], HandRules::default()), None);
+    assert_eq!(
+        tracker.expected_flush_completions(&[0, 9], four_fingers),
+        None
+    );
+}
+
+#[test]
+fn held_cards_changing_state_stay_in_hand() {
+    let mut deck = deck();
+    let mut tracker = DeckTracker::new();
+    let mut cards = opening_hand();
+    tracker.observe_cards(1, &deck, &hand(&cards), &[]);
+
+    // A boss debuffs the Hearts and a Tarot enhances a Spade while they are held.
+    for card in &mut cards[..4] {
+        card.debuffed = true;
+    }
+    cards[4].enhancement = Some(Enhancement::Glass);
+    for card in &mut deck {
+        if let Some(held) = cards
+            .iter()
+            .find(|held| held.rank == card.rank && held.suit == card.suit)
+        {
+            *card = held.clone();
+        }
+    }
+    tracker.observe_cards(1, &deck, &hand(&cards), &[]);
+    assert!(tracker.spent().is_empty());
+    assert_eq!(tracker.draw_pile().len(), 44);
+    assert_eq!(
+        tracker.hand()[4].as_ref().unwrap().enhancement,
+        Some(Enhancement::Glass)
+    );
+}
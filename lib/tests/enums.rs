@@ -0,0 +1,98 @@
+//! Keys, names and parsing generated for the game's enums.
+
+use remotro::balatro::{
+    blinds::{Boss, Tag},
+    boosters::BoosterPackKind,
+    consumables::{PlanetKind, SpectralKind, TarotKind},
+    deck::{CardEdition, Enhancement, Seal},
+    jokers::{JokerEdition, JokerKind},
+    menu::{Deck, Stake},
+    play::PokerHandKind,
+    shop::VoucherKind,
+};
+
+#[test]
+fn keys_round_trip() {
+    for pack in BoosterPackKind::iter() {
+        assert_eq!(BoosterPackKind::from_key(pack.key()), Some(pack));
+    }
+    assert_eq!(JokerKind::iter().count(), 150);
+    for joker in JokerKind::iter() {
+        assert_eq!(JokerKind::from_key(joker.key()), Some(joker));
+    }
+    assert_eq!(JokerKind::from_key("j_nope"), None);
+    assert_eq!(
+        JokerKind::from_key("j_8_ball"),
+        Some(JokerKind::EightBall { probability: 0 })
+    );
+    assert_eq!(Stake::Gold.key(), 8);
+    assert_eq!(Stake::from_key(2), Some(Stake::Red));
+}
+
+#[test]
+fn display_shows_the_game_names() {
+    assert_eq!(Deck::Red.to_string(), "Red");
+    assert_eq!(Boss::TheOx.to_string(), "The Ox");
+    assert_eq!(PlanetKind::PlanetX.to_string(), "Planet X");
+    assert_eq!(PokerHandKind::ThreeOfAKind.to_string(), "Three of a Kind");
+    assert_eq!(TarotKind::Heirophant.to_string(), "The Hierophant");
+    assert_eq!(JokerKind::GluttenousJoker.to_string(), "Gluttonous Joker");
+    assert_eq!(JokerKind::Caino { xmult: 1.0 }.to_string(), "Canio");
+    assert_eq!(
+        JokerKind::EightBall { probability: 0 }.to_string(),
+        "8 Ball"
+    );
+}
+
+/// Every variant's name parses back to it.
+#[test]
+fn names_round_trip() {
+    fn check<T>(variants: impl Iterator<Item = T>)
+    where
+        T: std::fmt::Debug + std::fmt::Display + std::str::FromStr + PartialEq,
+        T::Err: std::fmt::Debug,
+    {
+        for variant in variants {
+            assert_eq!(variant.to_string().parse::<T>().unwrap(), variant);
+        }
+    }
+    check(Boss::iter());
+    check(BoosterPackKind::iter());
+    check(CardEdition::iter());
+    check(Deck::iter());
+    check(Enhancement::iter());
+    check(JokerEdition::iter());
+    check(JokerKind::iter());
+    check(PlanetKind::iter());
+    check(PokerHandKind::iter());
+    check(Seal::iter());
+    check(SpectralKind::iter());
+    check(Stake::iter());
+    check(Tag::iter());
+    check(TarotKind::iter());
+    check(VoucherKind::iter());
+}
+
+#[test]
+fn names_parse_loosely() {
+    assert_eq!(" checkered ".parse(), Ok(Deck::Checkered));
+    assert_eq!("PURPLE".parse(), Ok(Stake::Purple));
+    assert_eq!("the ox".parse(), Ok(Boss::TheOx));
+    assert_eq!("mega-arcana".parse::<BoosterPackKind>(), Err(
+        "Invalid booster pack kind. Valid options are: Arcana Normal, Arcana Mega, Arcana Jumbo, \
+         Buffoon Normal, Buffoon Mega, Buffoon Jumbo, Celestial Normal, Celestial Mega, \
+         Celestial Jumbo, Spectral Normal, Spectral Mega, Spectral Jumbo, Standard Normal, \
+         Standard Mega, Standard Jumbo"
+            .to_string()
+    ));
+    assert_eq!("arcana mega".parse(), Ok(BoosterPackKind::ArcanaMega));
+    assert_eq!(
+        "Ride the Bus".parse(),
+        Ok(JokerKind::RideTheBus { mult: 0 })
+    );
+    assert_eq!(
+        "8 ball".parse(),
+        Ok(JokerKind::EightBall { probability: 0 })
+    );
+    assert_eq!("the hierophant".parse(), Ok(TarotKind::Heirophant));
+}
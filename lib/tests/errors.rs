@@ -0,0 +1,60 @@
+//! Game errors, and the screen handed back with them.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::{ActionError, CurrentScreen, Error, GameError, UnknownError},
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn game_errors_are_scripted() {
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Shop": fixtures::shop() }))
+        .err("shop/buymain", "shop/info", GameError::NotEnoughMoney)
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+
+    let CurrentScreen::Shop(shop) = balatro.screen().await.unwrap() else {
+        panic!("expected the shop");
+    };
+    let Err(ActionError { screen, error }) = shop.buy_main(0).await else {
+        panic!("expected the purchase to fail");
+    };
+    assert!(matches!(error, Error::Game(GameError::NotEnoughMoney)));
+    // The shop is handed back, so the bot can carry on from it.
+    assert_eq!(screen.main_cards().len(), 1);
+
+    drop(balatro);
+    game.finish().await;
+}
+
+#[tokio::test]
+async fn plain_string_errors_are_unknown() {
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Play": fixtures::play() }))
+        .respond(
+            "play/discard",
+            r#"result/play/discard/result!{"Err":"Out of discards"}"#,
+        )
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    let Err(ActionError {
+        error: Error::Game(GameError::Unknown(error)),
+        ..
+    }) = play.discard().await
+    else {
+        panic!("expected an unknown game error");
+    };
+    assert_eq!(error, UnknownError::Message("Out of discards".to_string()));
+
+    drop(balatro);
+    game.finish().await;
+}
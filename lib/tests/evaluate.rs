@@ -0,0 +1,225 @@
+//! Poker hand detection under the game's rules and the Jokers that change them.
+
+use remotro::balatro::{
+    deck::{Enhancement, PlayingCard, Rank, Suit},
+    jokers::{Joker, JokerKind},
+    play::{HandRules, PokerHandKind},
+};
+
+fn enhanced(rank: Rank, suit: Suit, enhancement: Enhancement) -> PlayingCard {
+    PlayingCard {
+        enhancement: Some(enhancement),
+        ..PlayingCard::new(rank, suit)
+    }
+}
+
+fn kind(cards: &[PlayingCard], rules: HandRules) -> PokerHandKind {
+    PokerHandKind::evaluate_with(cards, rules).unwrap().kind
+}
+
+#[test]
+fn no_cards_make_no_hand() {
+    assert!(PokerHandKind::evaluate(&[]).is_none());
+}
+
+#[test]
+fn standard_hands() {
+    use Rank::*;
+    use Suit::*;
+    let cases = [
+        (
+            vec![
+                PlayingCard::new(Ace, Spades),
+                PlayingCard::new(King, Hearts),
+            ],
+            PokerHandKind::HighCard,
+        ),
+        (
+            vec![PlayingCard::new(Two, Spades), PlayingCard::new(Two, Hearts)],
+            PokerHandKind::Pair,
+        ),
+        (
+            vec![
+                PlayingCard::new(Two, Spades),
+                PlayingCard::new(Two, Hearts),
+                PlayingCard::new(Nine, Clubs),
+                PlayingCard::new(Nine, Hearts),
+            ],
+            PokerHandKind::TwoPair,
+        ),
+        (
+            vec![
+                PlayingCard::new(Ace, Spades),
+                PlayingCard::new(Two, Hearts),
+                PlayingCard::new(Three, Clubs),
+                PlayingCard::new(Four, Hearts),
+                PlayingCard::new(Five, Diamonds),
+            ],
+            PokerHandKind::Straight,
+        ),
+        (
+            vec![
+                PlayingCard::new(Ten, Spades),
+                PlayingCard::new(Jack, Spades),
+                PlayingCard::new(Queen, Spades),
+                PlayingCard::new(King, Spades),
+                PlayingCard::new(Ace, Spades),
+            ],
+            PokerHandKind::StraightFlush,
+        ),
+        (
+            vec![
+                PlayingCard::new(Queen, Spades),
+                PlayingCard::new(King, Hearts),
+                PlayingCard::new(Ace, Clubs),
+                PlayingCard::new(Two, Hearts),
+                PlayingCard::new(Three, Diamonds),
+            ],
+            PokerHandKind::HighCard,
+        ),
+        (
+            vec![
+                PlayingCard::new(Six, Spades),
+                PlayingCard::new(Six, Hearts),
+                PlayingCard::new(Six, Clubs),
+                PlayingCard::new(Four, Hearts),
+                PlayingCard::new(Four, Diamonds),
+            ],
+            PokerHandKind::FullHouse,
+        ),
+        (
+            vec![PlayingCard::new(Eight, Hearts); 5],
+            PokerHandKind::FlushFive,
+        ),
+    ];
+    for (cards, expected) in cases {
+        assert_eq!(kind(&cards, HandRules::default()), expected, "{cards:?}");
+    }
+}
+
+#[test]
+fn only_the_hand_scores() {
+    let cards = [
+        PlayingCard::new(Rank::King, Suit::Spades),
+        PlayingCard::new(Rank::Seven, Suit::Hearts),
+        PlayingCard::new(Rank::King, Suit::Clubs),
+        enhanced(Rank::Two, Suit::Hearts, Enhancement::Stone),
+        PlayingCard::new(Rank::Ace, Suit::Diamonds),
+    ];
+    let hand = PokerHandKind::evaluate(&cards).unwrap();
+    assert_eq!(hand.kind, PokerHandKind::Pair);
+    // The Stone Card scores, but the Ace and the Seven do not.
+    assert_eq!(hand.scoring, [0, 2, 3]);
+    assert_eq!(hand.contains, [PokerHandKind::Pair]);
+
+    let high = PokerHandKind::evaluate(&cards[3..]).unwrap();
+    assert_eq!(high.kind, PokerHandKind::HighCard);
+    assert_eq!(high.scoring, [0, 1]);
+}
+
+#[test]
+fn stone_cards_do_not_make_hands() {
+    let stone = enhanced(Rank::Nine, Suit::Clubs, Enhancement::Stone);
+    let hand = PokerHandKind::evaluate(&[stone.clone(), stone]).unwrap();
+    assert_eq!(hand.kind, PokerHandKind::HighCard);
+    assert_eq!(hand.scoring, [0, 1]);
+}
+
+#[test]
+fn wild_cards_count_as_every_suit() {
+    let cards = [
+        PlayingCard::new(Rank::Two, Suit::Hearts),
+        PlayingCard::new(Rank::Five, Suit::Hearts),
+        enhanced(Rank::Nine, Suit::Spades, Enhancement::Wild),
+        PlayingCard::new(Rank::Jack, Suit::Hearts),
+        enhanced(Rank::King, Suit::Clubs, Enhancement::Wild),
+    ];
+    assert_eq!(kind(&cards, HandRules::default()), PokerHandKind::Flush);
+}
+
+#[test]
+fn four_fingers() {
+    let rules = HandRules {
+        four_fingers: true,
+        ..HandRules::default()
+    };
+    let flush = [
+        PlayingCard::new(Rank::Two, Suit::Hearts),
+        PlayingCard::new(Rank::Five, Suit::Hearts),
+        PlayingCard::new(Rank::Nine, Suit::Hearts),
+        PlayingCard::new(Rank::Jack, Suit::Hearts),
+        PlayingCard::new(Rank::King, Suit::Clubs),
+    ];
+    let hand = PokerHandKind::evaluate_with(&flush, rules).unwrap();
+    assert_eq!(hand.kind, PokerHandKind::Flush);
+    assert_eq!(hand.scoring, [0, 1, 2, 3]);
+    assert_eq!(kind(&flush, HandRules::default()), PokerHandKind::HighCard);
+
+    let straight = [
+        PlayingCard::new(Rank::Five, Suit::Hearts),
+        PlayingCard::new(Rank::Six, Suit::Spades),
+        PlayingCard::new(Rank::Seven, Suit::Clubs),
+        PlayingCard::new(Rank::Eight, Suit::Hearts),
+        PlayingCard::new(Rank::Eight, Suit::Diamonds),
+    ];
+    let hand = PokerHandKind::evaluate_with(&straight, rules).unwrap();
+    assert_eq!(hand.kind, PokerHandKind::Straight);
+    assert_eq!(hand.scoring, [0, 1, 2, 3, 4]);
+    assert!(hand.contains.contains(&PokerHandKind::Pair));
+}
+
+#[test]
+fn shortcut() {
+    let rules = HandRules {
+        shortcut: true,
+        ..HandRules::default()
+    };
+    let cards = [
+        PlayingCard::new(Rank::Ten, Suit::Hearts),
+        PlayingCard::new(Rank::Eight, Suit::Spades),
+        PlayingCard::new(Rank::Six, Suit::Clubs),
+        PlayingCard::new(Rank::Five, Suit::Hearts),
+        PlayingCard::new(Rank::Three, Suit::Diamonds),
+    ];
+    assert_eq!(kind(&cards, rules), PokerHandKind::Straight);
+    assert_eq!(kind(&cards, HandRules::default()), PokerHandKind::HighCard);
+
+    // Gaps of two ranks are still too wide.
+    let mut wide = cards.clone();
+    wide[0] = PlayingCard::new(Rank::Jack, Suit::Hearts);
+    assert_eq!(kind(&wide, rules), PokerHandKind::HighCard);
+}
+
+#[test]
+fn smeared() {
+    let rules = HandRules {
+        smeared: true,
+        ..HandRules::default()
+    };
+    let cards = [
+        PlayingCard::new(Rank::Two, Suit::Hearts),
+        PlayingCard::new(Rank::Five, Suit::Diamonds),
+        PlayingCard::new(Rank::Nine, Suit::Hearts),
+        PlayingCard::new(Rank::Jack, Suit::Diamonds),
+        PlayingCard::new(Rank::King, Suit::Diamonds),
+    ];
+    assert_eq!(kind(&cards, rules), PokerHandKind::Flush);
+    assert_eq!(kind(&cards, HandRules::default()), PokerHandKind::HighCard);
+    assert!(rules.has_suit(&cards[0], Suit::Diamonds));
+    assert!(!rules.has_suit(&cards[0], Suit::Clubs));
+}
+
+#[test]
+fn rules_from_jokers() {
+    let rules = HandRules::from_jokers(&[
+        Joker::new(JokerKind::Joker),
+        Joker::new(JokerKind::Shortcut),
+    ]);
+    assert_eq!(
+        rules,
+        HandRules {
+            shortcut: true,
+            ..HandRules::default()
+        }
+    );
+}
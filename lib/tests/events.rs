@@ -0,0 +1,25 @@
+//! Packets the game sends on its own, delivered as events.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::events::{GameEvent, ScreenKind},
+    testing::FakeGame,
+};
+use tokio_stream::StreamExt;
+
+#[tokio::test]
+async fn pushed_events_reach_the_client() {
+    let (balatro, game) = FakeGame::new().accepted(Remotro::builder()).await.unwrap();
+
+    let mut events = balatro.events();
+    game.push(r#"event/screen!{"screen":"Shop"}"#).await;
+    assert!(matches!(
+        events.next().await,
+        Some(GameEvent::ScreenChanged(ScreenKind::Shop))
+    ));
+
+    drop(events);
+    drop(balatro);
+    assert!(game.finish().await.is_empty());
+}
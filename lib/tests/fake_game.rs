@@ -0,0 +1,121 @@
+//! Loopback tests for the `testing` feature, driving a `Remotro` against a
+//! scripted `FakeGame`.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::{CurrentScreen, boosters::Open, play::PlayResult, shop::BoughtBooster},
+    net::{ConnectionConfig, transcript::Recorder},
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn screen_is_scripted() {
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Menu": fixtures::menu() }))
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.finish().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].kind, "screen/get");
+}
+
+#[tokio::test]
+async fn play_a_hand() {
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Play": fixtures::play() }))
+        .ok("play/click", "play/hand", fixtures::play())
+        .ok(
+            "play/play",
+            "play/play/result",
+            json!({ "Again": fixtures::play() }),
+        )
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    assert_eq!(play.hand().len(), 8);
+    let play = play.click(&[0, 1]).await.unwrap();
+    assert!(matches!(play.play().await.unwrap(), PlayResult::Again(_)));
+
+    drop(balatro);
+    let requests = game.finish().await;
+    let kinds: Vec<_> = requests.iter().map(|r| r.kind.as_str()).collect();
+    assert_eq!(kinds, ["screen/get", "play/click", "play/play"]);
+    assert_eq!(requests[1].body, json!({ "indices": [0, 1] }));
+}
+
+#[tokio::test]
+async fn buy_a_booster() {
+    let (mut balatro, game) = FakeGame::new()
+        .secret("hunter2")
+        .screen(json!({ "Shop": fixtures::shop() }))
+        .ok(
+            "shop/buybooster",
+            "shop/bought_booster",
+            json!({ "Buffoon": fixtures::buffoon_pack() }),
+        )
+        .accepted(Remotro::builder().secret("hunter2"))
+        .await
+        .unwrap();
+
+    let CurrentScreen::Shop(shop) = balatro.screen().await.unwrap() else {
+        panic!("expected the shop");
+    };
+    let BoughtBooster::Buffoon(pack) = shop.buy_booster(0).await.unwrap() else {
+        panic!("expected a Buffoon Pack");
+    };
+    assert_eq!(pack.options().len(), 2);
+
+    drop(balatro);
+    game.finish().await;
+}
+
+#[tokio::test]
+async fn replay_a_transcript() {
+    let path = std::env::temp_dir().join(format!("remotro-fake-game-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let recorder = Recorder::create(&path, &ConnectionConfig::default())
+        .await
+        .unwrap();
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Play": fixtures::play() }))
+        .ok("play/click", "play/hand", fixtures::play())
+        .accepted(Remotro::builder().record(recorder.clone()))
+        .await
+        .unwrap();
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    play.click(&[3]).await.unwrap();
+    drop(balatro);
+    game.finish().await;
+    // The recorder writes from a background task.
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(recorder.dropped(), 0);
+
+    let (mut balatro, game) = FakeGame::from_transcript(&path)
+        .unwrap()
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    assert_eq!(play.click(&[3]).await.unwrap().hand().len(), 8);
+
+    drop(balatro);
+    assert_eq!(game.finish().await.len(), 2);
+    let _ = std::fs::remove_file(&path);
+}
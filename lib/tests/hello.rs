@@ -0,0 +1,38 @@
+//! Protocol version negotiation in the hello exchange.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::CurrentScreen,
+    net,
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn incompatible_games_are_refused() {
+    let (mut remotro, _game) = FakeGame::new()
+        .ok("hello", "hello", json!({ "version": "99.0.0" }))
+        .host(Remotro::builder())
+        .await
+        .unwrap();
+    match remotro.accept().await {
+        Err(net::Error::VersionMismatch { theirs, .. }) => assert_eq!(theirs, "99.0.0"),
+        Err(e) => panic!("expected a version mismatch, got {e:?}"),
+        Ok(_) => panic!("expected a version mismatch"),
+    }
+}
+
+#[tokio::test]
+async fn games_without_hello_are_accepted() {
+    let (mut balatro, _game) = FakeGame::new()
+        .legacy()
+        .screen(json!({ "Menu": fixtures::menu() }))
+        .accepted(Remotro::builder().hello_timeout(Duration::from_millis(200)))
+        .await
+        .unwrap();
+
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+}
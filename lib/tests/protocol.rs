@@ -0,0 +1,64 @@
+//! Response bodies that still carry a `version`, as older mods send them,
+//! answered by a `FakeGame` over an in-process pipe.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    balatro::{Balatro, CurrentScreen, Error},
+    net::{self, ConnectionConfig, PROTOCOL_VERSION},
+    testing::FakeGame,
+};
+
+const MENU: &str = r#"{"Menu":{"saved_run":null}}"#;
+
+/// Plays a game without optional features, answering every `screen/get` with `reply`.
+async fn game_answering(reply: String) -> Balatro {
+    let (ours, theirs) = net::duplex(8);
+    FakeGame::new()
+        .respond(
+            "hello",
+            format!(r#"result/hello!{{"Ok":{{"version":"{PROTOCOL_VERSION}"}}}}"#),
+        )
+        .respond("screen/get", reply)
+        .spawn(theirs);
+    let mut connection = net::Connection::new(ours, &ConnectionConfig::default());
+    connection.handshake().await.unwrap();
+    Balatro::new(connection)
+}
+
+#[tokio::test]
+async fn version_before_the_variant_is_stripped() {
+    let mut balatro = game_answering(format!(
+        r#"result/screen/current!{{"version":"{PROTOCOL_VERSION}","Ok":{MENU}}}"#
+    ))
+    .await;
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+}
+
+#[tokio::test]
+async fn version_after_the_variant_is_stripped() {
+    let mut balatro = game_answering(format!(
+        r#"result/screen/current!{{"Ok":{MENU},"version":"{PROTOCOL_VERSION}"}}"#
+    ))
+    .await;
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+}
+
+#[tokio::test]
+async fn incompatible_versions_are_reported() {
+    let bodies = [
+        format!(r#"{{"version":"99.0.0","Ok":{MENU}}}"#),
+        format!(r#"{{"Ok":{MENU},"version":"99.0.0"}}"#),
+    ];
+    for body in bodies {
+        let mut balatro = game_answering(format!("result/screen/current!{body}")).await;
+        match balatro.screen().await {
+            Err(Error::Net(net::Error::VersionMismatch { theirs, .. })) => {
+                assert_eq!(theirs, "99.0.0")
+            }
+            Err(e) => panic!("expected a version mismatch for {body}, got {e:?}"),
+            Ok(_) => panic!("expected a version mismatch for {body}"),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Deadlines on requests and on accepting games, and responses that miss them.
+#![cfg(feature = "testing")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use remotro::{
+    Remotro,
+    balatro::{CurrentScreen, Error},
+    net,
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Keeps every warning logged in this test binary.
+struct Warnings(Mutex<Vec<String>>);
+
+impl Log for Warnings {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: Warnings = Warnings(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn unanswered_requests_time_out() {
+    // Nothing is scripted for `screen/get`, so the game never answers it.
+    let (mut balatro, game) = FakeGame::new()
+        .accepted(Remotro::builder().request_timeout(Duration::from_millis(200)))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        balatro.screen().await,
+        Err(Error::Net(net::Error::Timeout))
+    ));
+
+    drop(balatro);
+    game.finish().await;
+}
+
+#[tokio::test]
+async fn accept_times_out_without_a_game() {
+    let mut remotro = Remotro::builder()
+        .accept_timeout(Duration::from_millis(100))
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    assert!(matches!(remotro.accept().await, Err(net::Error::Timeout)));
+}
+
+#[tokio::test]
+async fn late_responses_do_not_answer_the_next_request() {
+    let _ = log::set_logger(&WARNINGS).map(|()| log::set_max_level(LevelFilter::Warn));
+    // The first screen is answered after its request gave up, while the
+    // second request is waiting.
+    let (mut balatro, game) = FakeGame::new()
+        .screen(json!({ "Menu": fixtures::menu() }))
+        .screen(json!({ "Play": fixtures::play() }))
+        .late("screen/get", Duration::from_millis(450))
+        .accepted(Remotro::builder().request_timeout(Duration::from_millis(300)))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        balatro.screen().await,
+        Err(Error::Net(net::Error::Timeout))
+    ));
+    assert!(matches!(balatro.screen().await, Ok(CurrentScreen::Play(_))));
+    assert!(
+        WARNINGS
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|warning| warning.starts_with("Dropping orphaned result/screen/current"))
+    );
+
+    drop(balatro);
+    assert_eq!(game.finish().await.len(), 2);
+}
@@ -0,0 +1,237 @@
+//! Score predictions for hands worked out by hand from the game's rules.
+
+use remotro::balatro::{
+    blinds::Boss,
+    deck::{CardEdition, Enhancement, PlayingCard, Rank, Seal, Suit},
+    hud::{CurrentPokerHand, CurrentPokerHands},
+    jokers::{Joker, JokerEdition, JokerKind},
+    menu::Deck,
+    play::{HandCard, PokerHand, PokerHandKind},
+};
+use remotro::scoring::{Effect, Scorer, Source};
+
+fn hand(cards: &[PlayingCard], selected: &[usize]) -> Vec<HandCard> {
+    cards
+        .iter()
+        .enumerate()
+        .map(|(index, card)| HandCard {
+            card: Some(card.clone()),
+            selected: selected.contains(&index),
+        })
+        .collect()
+}
+
+fn level_one(kind: PokerHandKind, chips: u64, mult: u64) -> CurrentPokerHand {
+    CurrentPokerHand {
+        hand: PokerHand {
+            kind,
+            level: 1,
+            chips,
+            mult,
+        },
+        played: 0,
+        played_round: 0,
+    }
+}
+
+fn poker_hands() -> CurrentPokerHands {
+    CurrentPokerHands {
+        high_card: level_one(PokerHandKind::HighCard, 5, 1),
+        pair: level_one(PokerHandKind::Pair, 10, 2),
+        two_pair: level_one(PokerHandKind::TwoPair, 20, 2),
+        three_of_a_kind: level_one(PokerHandKind::ThreeOfAKind, 30, 3),
+        straight: level_one(PokerHandKind::Straight, 30, 4),
+        flush: level_one(PokerHandKind::Flush, 35, 4),
+        full_house: level_one(PokerHandKind::FullHouse, 40, 4),
+        four_of_a_kind: level_one(PokerHandKind::FourOfAKind, 60, 7),
+        straight_flush: level_one(PokerHandKind::StraightFlush, 100, 8),
+        five_of_a_kind: None,
+        flush_house: None,
+        flush_fives: None,
+    }
+}
+
+#[test]
+fn pair_with_a_joker() {
+    let cards = [
+        PlayingCard::new(Rank::Ace, Suit::Spades),
+        PlayingCard::new(Rank::Ace, Suit::Hearts),
+        PlayingCard::new(Rank::King, Suit::Clubs),
+    ];
+    let jokers = [Joker::new(JokerKind::Joker)];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&jokers, &poker_hands)
+        .score(&hand(&cards, &[0, 1, 2]))
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::Pair);
+    assert_eq!(score.scoring, [0, 1]);
+    // (10 + 11 + 11) chips x (2 + 4) mult.
+    assert_eq!((score.chips, score.mult), (32.0, 6.0));
+    assert_eq!(score.total(), 192.0);
+    let last = score.steps.last().unwrap();
+    assert_eq!(last.source, Source::Joker(0));
+    assert_eq!(last.effect, Effect::Mult(4.0));
+}
+
+#[test]
+fn nothing_selected_scores_nothing() {
+    let cards = [PlayingCard::new(Rank::Ace, Suit::Spades)];
+    let poker_hands = poker_hands();
+    assert!(
+        Scorer::new(&[], &poker_hands)
+            .score(&hand(&cards, &[]))
+            .is_none()
+    );
+}
+
+#[test]
+fn locked_secret_hands_score_at_level_one() {
+    let cards = std::array::from_fn::<_, 5, _>(|_| PlayingCard::new(Rank::Ace, Suit::Spades));
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&[], &poker_hands)
+        .score(&hand(&cards, &[0, 1, 2, 3, 4]))
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::FlushFive);
+    // (160 + 5 x 11) chips x 16 mult.
+    assert_eq!((score.chips, score.mult), (215.0, 16.0));
+}
+
+#[test]
+fn cards_editions_and_seals() {
+    let mut glass = PlayingCard::new(Rank::Two, Suit::Diamonds);
+    glass.enhancement = Some(Enhancement::Glass);
+    glass.seal = Some(Seal::Red);
+    let mut foil = PlayingCard::new(Rank::Five, Suit::Diamonds);
+    foil.edition = Some(CardEdition::Foil);
+    let cards = [
+        glass,
+        PlayingCard::new(Rank::Three, Suit::Diamonds),
+        foil,
+        PlayingCard::new(Rank::Nine, Suit::Diamonds),
+        PlayingCard::new(Rank::Jack, Suit::Diamonds),
+    ];
+    let jokers = [Joker::new(JokerKind::GreedyJoker)];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&jokers, &poker_hands)
+        .score_selection(&hand(&cards, &[]), &[0, 1, 2, 3, 4])
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::Flush);
+    // The Glass Two scores twice from its Red Seal, Greedy Joker on each trigger.
+    // Chips: 35 + 2 + 2 + 3 + 5 + 50 + 9 + 10
+    // Mult: ((4 x 2 + 3) x 2 + 3) + 3 + 3 + 3 + 3
+    assert_eq!(score.chips, 116.0);
+    assert_eq!(score.mult, 37.0);
+    assert_eq!(
+        score
+            .steps
+            .iter()
+            .filter(|step| step.effect == Effect::Retrigger)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn held_cards_and_joker_order() {
+    let mut steel = PlayingCard::new(Rank::King, Suit::Spades);
+    steel.enhancement = Some(Enhancement::Steel);
+    let cards = [
+        PlayingCard::new(Rank::Seven, Suit::Hearts),
+        steel,
+        PlayingCard::new(Rank::Queen, Suit::Clubs),
+    ];
+    let mut polychrome = Joker::new(JokerKind::Joker);
+    polychrome.edition = Some(JokerEdition::Polychrome);
+    let jokers = [
+        Joker::new(JokerKind::Baron),
+        Joker::new(JokerKind::ShootTheMoon),
+        polychrome,
+    ];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&jokers, &poker_hands)
+        .score(&hand(&cards, &[0]))
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::HighCard);
+    // Held Steel King: x1.5 for Steel, x1.5 for Baron. Held Queen: +13.
+    // Then Joker +4 and Polychrome x1.5.
+    assert_eq!(score.chips, 12.0);
+    assert_eq!(score.mult, ((1.0 * 1.5 * 1.5) + 13.0 + 4.0) * 1.5);
+}
+
+#[test]
+fn blueprint_copies_the_joker_to_its_right() {
+    let cards = [
+        PlayingCard::new(Rank::Four, Suit::Spades),
+        PlayingCard::new(Rank::Four, Suit::Hearts),
+        PlayingCard::new(Rank::Four, Suit::Clubs),
+    ];
+    let jokers = [
+        Joker::new(JokerKind::Blueprint),
+        Joker::new(JokerKind::Trio),
+    ];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&jokers, &poker_hands)
+        .score(&hand(&cards, &[0, 1, 2]))
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::ThreeOfAKind);
+    assert_eq!(score.chips, 42.0);
+    assert_eq!(score.mult, 27.0);
+}
+
+#[test]
+fn stone_cards_always_score() {
+    let mut stone = PlayingCard::new(Rank::Two, Suit::Hearts);
+    stone.enhancement = Some(Enhancement::Stone);
+    let cards = [PlayingCard::new(Rank::Ten, Suit::Spades), stone];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&[], &poker_hands)
+        .score(&hand(&cards, &[0, 1]))
+        .unwrap();
+
+    assert_eq!(score.poker_hand, PokerHandKind::HighCard);
+    assert_eq!(score.scoring, [0, 1]);
+    assert_eq!(score.chips, 5.0 + 10.0 + 50.0);
+}
+
+#[test]
+fn bosses() {
+    let cards = [
+        PlayingCard::new(Rank::King, Suit::Spades),
+        PlayingCard::new(Rank::King, Suit::Hearts),
+    ];
+    let poker_hands = poker_hands();
+    let scorer = Scorer::new(&[], &poker_hands);
+
+    let plant = scorer.clone().boss(Boss::ThePlant);
+    let score = plant.score(&hand(&cards, &[0, 1])).unwrap();
+    assert_eq!((score.chips, score.mult), (10.0, 2.0));
+    assert_eq!(score.steps.last().unwrap().effect, Effect::Debuffed);
+
+    let flint = scorer.clone().boss(Boss::TheFlint);
+    let score = flint.score(&hand(&cards, &[0, 1])).unwrap();
+    assert_eq!((score.chips, score.mult), (25.0, 1.0));
+
+    let psychic = scorer.boss(Boss::ThePsychic);
+    let score = psychic.score(&hand(&cards, &[0, 1])).unwrap();
+    assert_eq!(score.total(), 0.0);
+    assert_eq!(score.steps.last().unwrap().effect, Effect::NotAllowed);
+}
+
+#[test]
+fn plasma_deck_balances() {
+    let cards = [PlayingCard::new(Rank::Ace, Suit::Spades)];
+    let jokers = [Joker::new(JokerKind::Joker)];
+    let poker_hands = poker_hands();
+    let score = Scorer::new(&jokers, &poker_hands)
+        .deck(Deck::Plasma)
+        .score(&hand(&cards, &[0]))
+        .unwrap();
+
+    // 16 chips and 5 mult balance to 10 each.
+    assert_eq!((score.chips, score.mult), (10.0, 10.0));
+}
@@ -0,0 +1,47 @@
+//! Accepting many games at once through a `Server` and its registry.
+#![cfg(feature = "testing")]
+
+use remotro::{Remotro, testing::FakeGame};
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+#[tokio::test]
+async fn registry_follows_connected_games() {
+    let mut server = Remotro::host("127.0.0.1", 0).await.unwrap().serve();
+    let addr = server.local_addr().unwrap();
+    let first = FakeGame::new().session("a").connect(addr).await.unwrap();
+    let second = FakeGame::new().session("b").connect(addr).await.unwrap();
+
+    let (one, _one) = server.next().await.unwrap();
+    let (two, _two) = server.next().await.unwrap();
+    assert_ne!(one, two);
+    let registry = server.registry().clone();
+    assert_eq!(registry.len(), 2);
+    let session = |id| registry.get(id).unwrap().peer.unwrap().session.unwrap();
+    let mut sessions = [session(one), session(two)];
+    sessions.sort();
+    assert_eq!(sessions, ["a", "b"]);
+
+    // The games may have been set up in either order.
+    let gone = if session(one) == "a" { one } else { two };
+    first.disconnect();
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while registry.get(gone).is_some() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the disconnected game leaves the registry");
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry.games()[0]
+            .peer
+            .as_ref()
+            .unwrap()
+            .session
+            .as_deref(),
+        Some("b")
+    );
+
+    drop(second);
+}
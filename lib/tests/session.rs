@@ -0,0 +1,76 @@
+//! Resuming a session when its game reconnects.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro, Session,
+    balatro::{CurrentScreen, Error},
+    net,
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+use std::time::Duration;
+
+fn game(token: &str) -> FakeGame {
+    FakeGame::new()
+        .session(token)
+        .screen(json!({ "Menu": fixtures::menu() }))
+}
+
+#[tokio::test]
+async fn same_token_resumes() {
+    let (mut remotro, first) = game("abc").host(Remotro::builder()).await.unwrap();
+    let mut session = Session::start(&mut remotro).await.unwrap();
+    assert_eq!(session.token(), "abc");
+    assert!(session.screen().await.is_ok());
+
+    first.disconnect();
+    assert!(matches!(session.screen().await, Err(Error::Net(_))));
+
+    let addr = remotro.local_addr().unwrap();
+    let second = game("abc").connect(addr).await.unwrap();
+    session.resume(&mut remotro).await.unwrap();
+    assert!(matches!(session.screen().await, Ok(CurrentScreen::Menu(_))));
+
+    drop(session);
+    assert_eq!(second.finish().await.len(), 1);
+}
+
+#[tokio::test]
+async fn other_tokens_are_turned_away() {
+    let (mut remotro, first) = game("abc").host(Remotro::builder()).await.unwrap();
+    let mut session = Session::start(&mut remotro).await.unwrap();
+    first.disconnect();
+
+    // Another game gets in first, and is hung up on without being asked anything.
+    let addr = remotro.local_addr().unwrap();
+    let other = FakeGame::new()
+        .session("xyz")
+        .screen(json!({ "Play": fixtures::play() }))
+        .connect(addr)
+        .await
+        .unwrap();
+    let ours = game("abc").connect(addr).await.unwrap();
+    session.resume(&mut remotro).await.unwrap();
+    assert_eq!(session.token(), "abc");
+    assert!(matches!(session.screen().await, Ok(CurrentScreen::Menu(_))));
+    assert!(other.finish().await.is_empty());
+
+    drop(session);
+    ours.finish().await;
+}
+
+#[tokio::test]
+async fn resume_times_out_without_the_game() {
+    let builder = Remotro::builder().session_grace(Duration::from_millis(300));
+    let (mut remotro, first) = game("abc").host(builder).await.unwrap();
+    let mut session = Session::start(&mut remotro).await.unwrap();
+    first.disconnect();
+
+    // Only a game from another session comes back.
+    let addr = remotro.local_addr().unwrap();
+    let _other = game("xyz").connect(addr).await.unwrap();
+    assert!(matches!(
+        session.resume(&mut remotro).await,
+        Err(net::Error::Timeout)
+    ));
+}
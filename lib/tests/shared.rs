@@ -0,0 +1,115 @@
+//! Shared handles, and screens that go stale when the game moves on.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    Remotro,
+    balatro::{ActionError, CurrentScreen, Error, SharedBalatro},
+    testing::{FakeGame, GameHandle, fixtures},
+};
+use serde_json::json;
+
+/// A shared handle on the play screen, with clicks scripted.
+async fn play() -> (SharedBalatro, GameHandle) {
+    let (balatro, game) = FakeGame::new()
+        .screen(json!({ "Play": fixtures::play() }))
+        .ok("play/click", "play/hand", fixtures::play())
+        .accepted(Remotro::builder())
+        .await
+        .unwrap();
+    (balatro.into_shared(), game)
+}
+
+#[tokio::test]
+async fn shared_screens_move_between_tasks() {
+    let (balatro, game) = play().await;
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    let clicked = tokio::spawn(async move { play.click(&[0]).await.is_ok() });
+    assert!(clicked.await.unwrap());
+
+    drop(balatro);
+    assert_eq!(game.finish().await.len(), 2);
+}
+
+#[tokio::test]
+async fn stale_shared_screens_are_refused() {
+    let (balatro, game) = play().await;
+    let (CurrentScreen::Play(first), CurrentScreen::Play(second)) = (
+        balatro.screen().await.unwrap(),
+        balatro.screen().await.unwrap(),
+    ) else {
+        panic!("expected the play screen");
+    };
+    first.click(&[0]).await.unwrap();
+    let Err(ActionError { screen, error }) = second.click(&[1]).await else {
+        panic!("expected the stale screen to be refused");
+    };
+    assert!(matches!(error, Error::StaleScreen));
+    // Reading a stale screen still works.
+    assert_eq!(screen.hand().len(), 8);
+
+    // Screens share the connection, so it stays open until the last is dropped.
+    drop(screen);
+    drop(balatro);
+    let kinds: Vec<_> = game.finish().await.into_iter().map(|r| r.kind).collect();
+    assert_eq!(kinds, ["screen/get", "screen/get", "play/click"]);
+}
+
+#[tokio::test]
+async fn screens_go_stale_when_the_game_moves_on() {
+    let (balatro, game) = play().await;
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    // The player clicks a card in the game itself.
+    game.advance().await;
+    let Err(ActionError { screen, error }) = play.click(&[0]).await else {
+        panic!("expected the stale screen to be refused");
+    };
+    assert!(matches!(error, Error::StaleScreen));
+    drop(screen);
+
+    // A fresh screen is current again.
+    let CurrentScreen::Play(play) = balatro.screen().await.unwrap() else {
+        panic!("expected the play screen");
+    };
+    let play = play.click(&[0]).await.unwrap();
+    drop(play);
+
+    drop(balatro);
+    let requests = game.finish().await;
+    let kinds: Vec<_> = requests.iter().map(|r| r.kind.as_str()).collect();
+    assert_eq!(
+        kinds,
+        ["screen/get", "play/click", "screen/get", "play/click"]
+    );
+    assert_eq!(requests[3].body, json!({ "indices": [0] }));
+}
+
+#[tokio::test]
+async fn a_stale_refusal_makes_every_shared_screen_stale() {
+    let (balatro, game) = play().await;
+    let (CurrentScreen::Play(first), CurrentScreen::Play(second)) = (
+        balatro.screen().await.unwrap(),
+        balatro.screen().await.unwrap(),
+    ) else {
+        panic!("expected the play screen");
+    };
+    game.advance().await;
+    let Err(ActionError { screen, error }) = first.click(&[0]).await else {
+        panic!("expected the game to refuse the stale screen");
+    };
+    assert!(matches!(error, Error::StaleScreen));
+    drop(screen);
+    // Refused without asking the game again.
+    let Err(ActionError { screen, error }) = second.click(&[0]).await else {
+        panic!("expected the stale screen to be refused");
+    };
+    assert!(matches!(error, Error::StaleScreen));
+    drop(screen);
+
+    drop(balatro);
+    let kinds: Vec<_> = game.finish().await.into_iter().map(|r| r.kind).collect();
+    assert_eq!(kinds, ["screen/get", "screen/get", "play/click"]);
+}
@@ -0,0 +1,129 @@
+//! Loopback tests for the `tls` feature, using the self-signed certificate in
+//! `tests/data` (valid for `localhost` and `127.0.0.1`).
+#![cfg(all(feature = "tls", feature = "testing"))]
+
+use remotro::{
+    Remotro,
+    balatro::CurrentScreen,
+    net::{self, ConnectionConfig, StreamTransport, TlsConfig},
+    testing::{FakeGame, GameHandle, fixtures},
+};
+use serde_json::json;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{io::AsyncWriteExt, net::TcpStream};
+use tokio_rustls::{
+    TlsConnector,
+    rustls::{ClientConfig, RootCertStore, crypto::ring, pki_types::ServerName},
+};
+
+const CERT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/localhost.crt");
+const KEY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/localhost.key");
+
+fn connector() -> TlsConnector {
+    let mut roots = RootCertStore::empty();
+    let mut pem = std::io::BufReader::new(std::fs::File::open(CERT).unwrap());
+    for cert in rustls_pemfile::certs(&mut pem) {
+        roots.add(cert.unwrap()).unwrap();
+    }
+    let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+}
+
+/// Connects a `FakeGame` over TLS that reports the main menu for every `screen/get`.
+async fn fake_game(addr: SocketAddr) -> GameHandle {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let server_name = ServerName::try_from("localhost").unwrap();
+    let stream = connector().connect(server_name, stream).await.unwrap();
+    FakeGame::new()
+        .ok(
+            "screen/get",
+            "screen/current",
+            json!({ "Menu": fixtures::menu() }),
+        )
+        .spawn(StreamTransport::new(stream, &ConnectionConfig::default()))
+}
+
+fn tls_config() -> TlsConfig {
+    TlsConfig::from_pem_files(CERT, KEY).unwrap()
+}
+
+#[tokio::test]
+async fn screen_over_tls() {
+    let mut remotro = Remotro::builder()
+        .tls(tls_config())
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let game = tokio::spawn(fake_game(addr));
+
+    let mut balatro = remotro.accept().await.unwrap();
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.await.unwrap().finish().await;
+    assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn heartbeat_over_tls() {
+    let mut remotro = Remotro::builder()
+        .tls(tls_config())
+        .inactivity_timeout(Duration::from_millis(50))
+        .ping_response_timeout(Duration::from_millis(200))
+        .max_ping_retries(2)
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let game = tokio::spawn(fake_game(addr));
+
+    let mut balatro = remotro.accept().await.unwrap();
+    // Without pongs, the connection would time out after about 450ms of this.
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    assert!(!balatro.is_closed());
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    game.await.unwrap().finish().await;
+}
+
+#[tokio::test]
+async fn plaintext_peer_is_rejected() {
+    let mut remotro = Remotro::builder()
+        .tls(tls_config())
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let game = tokio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"result/screen/current!{}\n")
+            .await
+            .unwrap();
+    });
+
+    assert!(remotro.accept().await.is_err());
+    game.await.unwrap();
+}
+
+#[tokio::test]
+async fn silent_peer_times_out_of_the_tls_handshake() {
+    let mut remotro = Remotro::builder()
+        .tls(tls_config())
+        .handshake_timeout(Duration::from_millis(200))
+        .host("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let _peer = TcpStream::connect(addr).await.unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), remotro.accept()).await;
+    assert!(matches!(result, Ok(Err(net::Error::Timeout))));
+}
@@ -0,0 +1,52 @@
+//! Driving a game over transports other than a hosted TCP socket.
+#![cfg(feature = "testing")]
+
+use remotro::{
+    balatro::{Balatro, CurrentScreen},
+    net::{self, ConnectionConfig},
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn balatro_over_an_in_process_pipe() {
+    let (ours, theirs) = net::duplex(8);
+    let game = FakeGame::new()
+        .screen(json!({ "Menu": fixtures::menu() }))
+        .spawn(theirs);
+
+    let mut connection = net::Connection::new(ours, &ConnectionConfig::default());
+    connection.handshake().await.unwrap();
+    let mut balatro = Balatro::new(connection);
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.finish().await;
+    assert_eq!(requests.len(), 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn screen_over_a_unix_socket() {
+    let path = std::env::temp_dir().join(format!("remotro-{}.sock", std::process::id()));
+    let mut remotro = remotro::Remotro::host_unix(&path).await.unwrap();
+    assert!(remotro.local_addr().is_none());
+    let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+    let game = FakeGame::new()
+        .screen(json!({ "Menu": fixtures::menu() }))
+        .spawn(net::StreamTransport::new(
+            stream,
+            &ConnectionConfig::default(),
+        ));
+
+    let mut balatro = remotro.accept().await.unwrap();
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    game.finish().await;
+    assert!(path.exists());
+    drop(remotro);
+    assert!(!path.exists());
+}
@@ -0,0 +1,72 @@
+//! Loopback tests for the `websocket` feature, with a `FakeGame` on the other
+//! end of a real WebSocket.
+#![cfg(all(feature = "websocket", feature = "testing"))]
+
+use remotro::{
+    Remotro,
+    balatro::CurrentScreen,
+    net::{self, ConnectionConfig, WebSocketTransport},
+    testing::{FakeGame, fixtures},
+};
+use serde_json::json;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_stream::StreamExt;
+
+#[tokio::test]
+async fn screen_over_a_websocket() {
+    let mut remotro = Remotro::host_websocket("127.0.0.1", 0).await.unwrap();
+    let addr = remotro.local_addr().unwrap();
+    // The WebSocket handshake needs the host to be accepting, so connect alongside.
+    let connect = tokio::spawn(tokio_tungstenite::connect_async(format!("ws://{addr}")));
+    let (balatro, game) = tokio::join!(remotro.accept(), async {
+        let (ws, _) = connect.await.unwrap().unwrap();
+        FakeGame::new()
+            .ok(
+                "screen/get",
+                "screen/current",
+                json!({ "Menu": fixtures::menu() }),
+            )
+            .spawn(WebSocketTransport::new(ws, &ConnectionConfig::default()))
+    });
+    let mut balatro = balatro.unwrap();
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.finish().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].kind, "screen/get");
+}
+
+#[tokio::test]
+async fn silent_peer_times_out_of_the_websocket_handshake() {
+    let mut remotro = Remotro::builder()
+        .handshake_timeout(Duration::from_millis(200))
+        .host_websocket("127.0.0.1", 0)
+        .await
+        .unwrap();
+    let addr = remotro.local_addr().unwrap();
+    let _peer = TcpStream::connect(addr).await.unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), remotro.accept()).await;
+    assert!(matches!(result, Ok(Err(net::Error::Timeout))));
+}
+
+#[tokio::test]
+async fn silent_peer_does_not_hold_up_the_server() {
+    let mut server = Remotro::host_websocket("127.0.0.1", 0)
+        .await
+        .unwrap()
+        .serve();
+    let addr = server.local_addr().unwrap();
+    let _silent = TcpStream::connect(addr).await.unwrap();
+    let (ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+        .await
+        .unwrap();
+    let _game = FakeGame::new().spawn(WebSocketTransport::new(ws, &ConnectionConfig::default()));
+
+    // Well within the default handshake timeout the silent peer is stuck in.
+    let next = tokio::time::timeout(Duration::from_secs(2), server.next()).await;
+    assert!(matches!(next, Ok(Some(_))));
+    assert_eq!(server.registry().len(), 1);
+}
@@ -0,0 +1,219 @@
+//! Pairs remote controllers with games when neither side can accept
+//! connections. Both connect out to the relay and send a
+//! [`Join`](remotro::net::relay::Join) naming a room. Once a game and a
+//! controller are in the same room, `kind!body` frames are forwarded between
+//! them. Each leg has its own heartbeat, answered and sent by the relay, so
+//! pings never cross over to the other side.
+
+use log::{debug, info, warn};
+use remotro::net::{
+    ConnectionConfig, Error, StreamTransport, Transport,
+    relay::{Join, Role},
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+/// How long a new leg has to send its join packet.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause after a failed accept, e.g. when out of file descriptors, so the
+/// loop doesn't spin while the error persists.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Packets a waiting leg may send before its partner joins, e.g. the
+/// controller's hello. They are passed on once the room is paired.
+const MAX_PENDING: usize = 32;
+
+/// A leg waiting in a room for its partner. The leg stays with its own task,
+/// which notices if it leaves, and is handed the partner through `pair`.
+struct Waiting {
+    role: Role,
+    /// Tells apart legs that wait in the same room one after another.
+    id: u64,
+    pair: oneshot::Sender<StreamTransport>,
+}
+
+type Rooms = Arc<Mutex<HashMap<String, Waiting>>>;
+
+pub struct Relay {
+    listener: TcpListener,
+    config: ConnectionConfig,
+    rooms: Rooms,
+}
+
+impl Relay {
+    /// Listens on `addr`. Heartbeat settings of `config` apply to every leg.
+    pub async fn bind(addr: &str, config: ConnectionConfig) -> Result<Self, Error> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            config,
+            rooms: Arc::default(),
+        })
+    }
+
+    /// The address legs connect to, e.g. to learn the port picked when
+    /// binding to port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts legs. Failed accepts are logged and retried, so this only
+    /// returns if the listener's address can't be read.
+    pub async fn run(self) -> Result<(), Error> {
+        info!("Relay listening on {}", self.listener.local_addr()?);
+        loop {
+            let (stream, addr) = match self.listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept leg: {e}");
+                    tokio::time::sleep(ACCEPT_BACKOFF).await;
+                    continue;
+                }
+            };
+            debug!("Leg connected from {addr}");
+            tokio::spawn(join(stream, self.config.clone(), self.rooms.clone()));
+        }
+    }
+}
+
+/// Reads the join packet of a new leg, then either pairs it with the leg
+/// already waiting in its room, or waits there for its partner itself. A second
+/// leg of the same role is turned away.
+async fn join(stream: TcpStream, config: ConnectionConfig, rooms: Rooms) {
+    static LEGS: AtomicU64 = AtomicU64::new(0);
+
+    let mut leg = StreamTransport::new(stream, &config);
+    let join: Join = match tokio::time::timeout(JOIN_TIMEOUT, leg.recv()).await {
+        Ok(Ok(packet)) => match Join::decode(&packet) {
+            Ok(join) => join,
+            Err(e) => {
+                warn!("Leg did not join a room: {e}");
+                return;
+            }
+        },
+        Ok(Err(e)) => {
+            warn!("Leg left before joining: {e}");
+            return;
+        }
+        Err(_) => {
+            warn!("Leg did not join in time");
+            return;
+        }
+    };
+
+    let id = LEGS.fetch_add(1, Ordering::Relaxed);
+    let (pair, mut paired) = oneshot::channel();
+    {
+        let mut rooms = lock(&rooms);
+        leg = match rooms.remove(&join.room) {
+            Some(waiting) if waiting.role == join.role.partner() => match waiting.pair.send(leg) {
+                Ok(()) => return,
+                // The waiting leg left just now, so wait in its place.
+                Err(leg) => leg,
+            },
+            Some(waiting) => {
+                warn!(
+                    "Room {} already has a {:?}, turning the new one away",
+                    join.room, join.role
+                );
+                rooms.insert(join.room, waiting);
+                return;
+            }
+            None => leg,
+        };
+        info!("{:?} waiting in room {}", join.role, join.room);
+        rooms.insert(
+            join.room.clone(),
+            Waiting {
+                role: join.role,
+                id,
+                pair,
+            },
+        );
+    }
+
+    let mut pending = Vec::new();
+    let mut partner = loop {
+        tokio::select! {
+            partner = &mut paired => match partner {
+                Ok(partner) => break partner,
+                Err(_) => return,
+            },
+            packet = leg.recv() => {
+                match packet {
+                    Ok(packet) if pending.len() < MAX_PENDING => {
+                        pending.push(packet);
+                        continue;
+                    }
+                    Ok(_) => warn!(
+                        "{:?} sent too much before room {} was paired",
+                        join.role, join.room
+                    ),
+                    Err(e) => info!(
+                        "{:?} left room {} before it was paired: {e}",
+                        join.role, join.room
+                    ),
+                }
+                leave(&rooms, &join.room, id);
+                return;
+            }
+        }
+    };
+
+    info!("Paired room {}", join.room);
+    for packet in pending {
+        if partner.send(packet).await.is_err() {
+            return;
+        }
+    }
+    let (game, controller) = match join.role {
+        Role::Game => (leg, partner),
+        Role::Controller => (partner, leg),
+    };
+    forward(game, controller).await;
+    info!("Room {} closed", join.room);
+}
+
+/// Empties `room` if leg `id` is still the one waiting in it.
+fn leave(rooms: &Rooms, room: &str, id: u64) {
+    let mut rooms = lock(rooms);
+    if rooms.get(room).is_some_and(|waiting| waiting.id == id) {
+        rooms.remove(room);
+    }
+}
+
+fn lock(rooms: &Rooms) -> std::sync::MutexGuard<'_, HashMap<String, Waiting>> {
+    // The map is never left half updated, so a poisoned lock is still usable.
+    rooms.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Copies frames both ways until either leg closes, which closes the other.
+async fn forward(mut game: StreamTransport, mut controller: StreamTransport) {
+    loop {
+        tokio::select! {
+            packet = game.recv() => match packet {
+                Ok(packet) => {
+                    if controller.send(packet).await.is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            },
+            packet = controller.recv() => match packet {
+                Ok(packet) => {
+                    if game.send(packet).await.is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            },
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use log::error;
+use remotro::net::ConnectionConfig;
+use remotro_relay::Relay;
+use std::process::ExitCode;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+
+    // Address to listen on, e.g. `remotro-relay 0.0.0.0:34144`
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or("0.0.0.0:34144".to_string());
+    let relay = match Relay::bind(&addr, ConnectionConfig::default()).await {
+        Ok(relay) => relay,
+        Err(e) => {
+            error!("Failed to listen on {addr}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = relay.run().await {
+        error!("Relay stopped: {e}");
+    }
+    ExitCode::FAILURE
+}
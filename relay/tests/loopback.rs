@@ -0,0 +1,150 @@
+//! Pairs a fake game and a real controller through a relay on loopback.
+
+use remotro::{
+    Remotro,
+    balatro::CurrentScreen,
+    net::{
+        ConnectionConfig, StreamTransport, Transport,
+        relay::{Join, Role},
+    },
+    testing::{FakeGame, GameHandle, fixtures},
+};
+use remotro_relay::Relay;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+async fn start_relay(config: ConnectionConfig) -> SocketAddr {
+    let relay = Relay::bind("127.0.0.1:0", config).await.unwrap();
+    let addr = relay.local_addr().unwrap();
+    tokio::spawn(relay.run());
+    addr
+}
+
+/// Connects a bare leg to the relay and joins `room` as `role`.
+async fn join(addr: SocketAddr, room: &str, role: Role) -> StreamTransport {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let mut leg = StreamTransport::new(stream, &ConnectionConfig::default());
+    let join = Join {
+        room: room.to_string(),
+        role,
+    };
+    leg.send(join.encode().unwrap()).await.unwrap();
+    leg
+}
+
+/// Joins `room` as the game, then plays a `FakeGame` that reports the main
+/// menu for every `screen/get`.
+async fn fake_game(addr: SocketAddr, room: &str) -> GameHandle {
+    let leg = join(addr, room, Role::Game).await;
+    FakeGame::new()
+        .ok(
+            "screen/get",
+            "screen/current",
+            json!({ "Menu": fixtures::menu() }),
+        )
+        .spawn(leg)
+}
+
+#[tokio::test]
+async fn controller_drives_game_through_relay() {
+    let addr = start_relay(ConnectionConfig::default()).await;
+    let game = fake_game(addr, "ABCD").await;
+
+    let mut balatro = Remotro::builder().room("ABCD").connect(addr).await.unwrap();
+    // The game's answer to the hello exchange made it through.
+    assert!(
+        balatro
+            .peer()
+            .is_some_and(|peer| !peer.capabilities.is_empty())
+    );
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    let requests = game.finish().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].kind, "screen/get");
+}
+
+#[tokio::test]
+async fn heartbeats_stay_on_their_leg() {
+    let config = ConnectionConfig {
+        inactivity_timeout: Duration::from_millis(50),
+        ping_response_timeout: Duration::from_millis(200),
+        max_ping_retries: 2,
+        ..ConnectionConfig::default()
+    };
+    let addr = start_relay(config.clone()).await;
+    let game = fake_game(addr, "EFGH").await;
+
+    let mut balatro = Remotro::builder()
+        .config(config)
+        .room("EFGH")
+        .connect(addr)
+        .await
+        .unwrap();
+    // Without pongs on both legs, the relay and the controller would each
+    // give up after about 450ms of this.
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    assert!(!balatro.is_closed());
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    game.finish().await;
+}
+
+#[tokio::test]
+async fn different_rooms_are_not_paired() {
+    let addr = start_relay(ConnectionConfig::default()).await;
+    let _game = fake_game(addr, "IJKL").await;
+
+    let result = Remotro::builder()
+        .request_timeout(Duration::from_millis(300))
+        .room("MNOP")
+        .connect(addr)
+        .await;
+    assert!(matches!(result, Err(remotro::net::Error::Timeout)));
+}
+
+#[tokio::test]
+async fn games_that_leave_are_not_paired() {
+    let addr = start_relay(ConnectionConfig::default()).await;
+    drop(join(addr, "QRST", Role::Game).await);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let game = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        fake_game(addr, "QRST").await
+    });
+    let mut balatro = Remotro::builder()
+        .request_timeout(Duration::from_secs(2))
+        .room("QRST")
+        .connect(addr)
+        .await
+        .unwrap();
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    game.await.unwrap().finish().await;
+}
+
+#[tokio::test]
+async fn second_game_in_a_room_is_turned_away() {
+    let addr = start_relay(ConnectionConfig::default()).await;
+    let game = fake_game(addr, "UVWX").await;
+    let mut intruder = join(addr, "UVWX", Role::Game).await;
+    let turned_away = tokio::time::timeout(Duration::from_secs(2), intruder.recv()).await;
+    assert!(matches!(turned_away, Ok(Err(_))));
+
+    // The game that joined first still gets its controller.
+    let mut balatro = Remotro::builder().room("UVWX").connect(addr).await.unwrap();
+    let screen = balatro.screen().await.unwrap();
+    assert!(matches!(screen, CurrentScreen::Menu(_)));
+
+    drop(balatro);
+    assert_eq!(game.finish().await.len(), 1);
+}
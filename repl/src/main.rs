@@ -6,9 +6,9 @@ use remotro::{
         hud::{Hud, RunInfo}, 
         menu::{Deck, Stake}, 
         play::{DiscardResult, PlayResult}, 
-        CurrentScreen
+        CurrentScreen, Error
     },
-    Remotro
+    Remotro, Session
 };
 use std::str::FromStr;
 
@@ -121,10 +121,11 @@ fn display_consumable_menu() {
 
 fn print_run_info(run_info: &RunInfo) {
     println!("\n{}", "--- RUN INFO ---".bright_white().bold());
-    println!("{}: {:?}", "Stake".yellow().bold(), run_info.stake);
+    println!("{}: {}", "Stake".yellow().bold(), run_info.stake);
     
     if !run_info.vouchers_redeemed.is_empty() {
-        println!("{}: {:?}", "Vouchers Redeemed".cyan().bold(), run_info.vouchers_redeemed);
+        let vouchers: Vec<&str> = run_info.vouchers_redeemed.iter().map(|voucher| voucher.info().name).collect();
+        println!("{}: {}", "Vouchers Redeemed".cyan().bold(), vouchers.join(", "));
     }
     
     println!("\n{}:", "Poker Hands".bright_green().bold());
@@ -176,26 +177,26 @@ fn print_run_info(run_info: &RunInfo) {
                  hands.straight_flush.hand.mult, hands.straight_flush.played);
     }
     
-    if let Some(five_kind) = &hands.five_of_a_kind {
-        if five_kind.played > 0 {
-            println!("  Five of a Kind: Lv.{} ({} chips, {}x mult) - Played {} times", 
-                     five_kind.hand.level, five_kind.hand.chips, 
-                     five_kind.hand.mult, five_kind.played);
-        }
+    if let Some(five_kind) = &hands.five_of_a_kind
+        && five_kind.played > 0
+    {
+        println!("  Five of a Kind: Lv.{} ({} chips, {}x mult) - Played {} times", 
+                 five_kind.hand.level, five_kind.hand.chips, 
+                 five_kind.hand.mult, five_kind.played);
     }
-    if let Some(flush_house) = &hands.flush_house {
-        if flush_house.played > 0 {
-            println!("  Flush House: Lv.{} ({} chips, {}x mult) - Played {} times", 
-                     flush_house.hand.level, flush_house.hand.chips, 
-                     flush_house.hand.mult, flush_house.played);
-        }
+    if let Some(flush_house) = &hands.flush_house
+        && flush_house.played > 0
+    {
+        println!("  Flush House: Lv.{} ({} chips, {}x mult) - Played {} times", 
+                 flush_house.hand.level, flush_house.hand.chips, 
+                 flush_house.hand.mult, flush_house.played);
     }
-    if let Some(flush_fives) = &hands.flush_fives {
-        if flush_fives.played > 0 {
-            println!("  Flush Fives: Lv.{} ({} chips, {}x mult) - Played {} times", 
-                     flush_fives.hand.level, flush_fives.hand.chips, 
-                     flush_fives.hand.mult, flush_fives.played);
-        }
+    if let Some(flush_fives) = &hands.flush_fives
+        && flush_fives.played > 0
+    {
+        println!("  Flush Fives: Lv.{} ({} chips, {}x mult) - Played {} times", 
+                 flush_fives.hand.level, flush_fives.hand.chips, 
+                 flush_fives.hand.mult, flush_fives.played);
     }
     
     println!("\nBlinds Progress:");
@@ -215,19 +216,22 @@ fn print_hud<'a, T: Hud<'a>>(hud: &T) {
              "Ante".magenta().bold(), hud.ante());
     
     if !hud.jokers().is_empty() {
-        println!("{}: {:?}", "Jokers".yellow().bold(), hud.jokers());
+        let jokers: Vec<&str> = hud.jokers().iter().map(|joker| joker.kind.info().name).collect();
+        println!("{}: {}", "Jokers".yellow().bold(), jokers.join(", "));
     }
     if !hud.consumables().is_empty() {
-        println!("{}: {:?}", "Consumables".magenta().bold(), hud.consumables());
+        let consumables: Vec<&str> = hud.consumables().iter().map(|consumable| consumable.info().name).collect();
+        println!("{}: {}", "Consumables".magenta().bold(), consumables.join(", "));
     }
     if !hud.tags().is_empty() {
-        println!("{}: {:?}", "Tags".cyan().bold(), hud.tags());
+        let tags: Vec<&str> = hud.tags().iter().map(|tag| tag.info().name).collect();
+        println!("{}: {}", "Tags".cyan().bold(), tags.join(", "));
     }
     print_run_info(hud.run_info());
     println!("{}", "----------------".white().bold());
 }
 
-async fn handle_hud_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box<dyn std::error::Error>> {
+async fn handle_hud_management<'a, T: Hud<'a>>(mut screen: T) -> T {
     loop {
         display_hud_menu();
         print_hud(&screen);
@@ -235,15 +239,16 @@ async fn handle_hud_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box<d
         let action = get_string_input("Enter action:");
         match action.trim().to_lowercase().as_str() {
             "jokers" => {
-                screen = handle_joker_management(screen).await?;
+                screen = handle_joker_management(screen).await;
             },
             "consumables" => {
-                screen = handle_consumable_management(screen).await?;
+                screen = handle_consumable_management(screen).await;
             },
             "tags" => {
                 println!("\n{}", "--- TAGS ---".bright_cyan().bold());
                 for (i, tag) in screen.tags().iter().enumerate() {
-                    println!("  {}: {:?}", i, tag);
+                    let info = tag.info();
+                    println!("  {}: {} - {}", i, info.name.bold(), info.effect);
                 }
                 if screen.tags().is_empty() {
                     println!("  No tags available");
@@ -251,28 +256,32 @@ async fn handle_hud_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box<d
                 println!("Press Enter to continue");
                 let _ = get_string_input("");
             },
-            "back" => return Ok(screen),
+            "back" => return screen,
             _ => println!("Invalid action. Use 'jokers', 'consumables', 'tags', or 'back'."),
         }
     }
 }
 
-async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box<dyn std::error::Error>> {
+async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> T {
     loop {
         display_joker_menu();
         println!("\n{}", "--- JOKERS ---".bright_yellow().bold());
         let jokers = screen.jokers().to_vec();
         for (i, joker) in jokers.iter().enumerate() {
-            println!("  {}: {:?}", i, joker);
+            let info = joker.kind.info();
+            println!("  {}: {} - {}", i, info.name.bold(), info.effect);
+            if let Some(edition) = joker.edition {
+                println!("      Edition: {:?}", edition);
+            }
         }
         if jokers.is_empty() {
             println!("  No jokers available");
         }
         
         let input = get_string_input("Enter action:");
-        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+        let parts: Vec<&str> = input.split_whitespace().collect();
         
-        screen = match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+        screen = match parts.first().map(|s| s.to_lowercase()).as_deref() {
             Some("move") => {
                 if parts.len() == 3 {
                     if let (Ok(from), Ok(to)) = (parts[1].parse::<u32>(), parts[2].parse::<u32>()) {
@@ -283,7 +292,7 @@ async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box
                             },
                             Err(e) => {
                                 error!("Failed to move joker: {}", e);
-                                return Err(e.into());
+                                e.screen
                             },
                         }
                     } else {
@@ -305,7 +314,7 @@ async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box
                             },
                             Err(e) => {
                                 error!("Failed to sell joker: {}", e);
-                                return Err(e.into());
+                                e.screen
                             },
                         }
                     } else {
@@ -317,7 +326,7 @@ async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box
                     screen
                 }
             },
-            Some("back") => return Ok(screen),
+            Some("back") => return screen,
             _ => {
                 println!("Invalid action. Use 'move <from> <to>', 'sell <index>', or 'back'.");
                 screen
@@ -326,22 +335,23 @@ async fn handle_joker_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box
     }
 }
 
-async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T, Box<dyn std::error::Error>> {
+async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> T {
     loop {
         display_consumable_menu();
         println!("\n{}", "--- CONSUMABLES ---".bright_magenta().bold());
         let consumables = screen.consumables().to_vec();
         for (i, consumable) in consumables.iter().enumerate() {
-            println!("  {}: {:?}", i, consumable);
+            let info = consumable.info();
+            println!("  {}: {} - {}", i, info.name.bold(), info.effect);
         }
         if consumables.is_empty() {
             println!("  No consumables available");
         }
         
         let input = get_string_input("Enter action:");
-        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+        let parts: Vec<&str> = input.split_whitespace().collect();
         
-        screen = match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+        screen = match parts.first().map(|s| s.to_lowercase()).as_deref() {
             Some("use") => {
                 if parts.len() == 2 {
                     if let Ok(index) = parts[1].parse::<u32>() {
@@ -352,7 +362,7 @@ async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T
                             },
                             Err(e) => {
                                 error!("Failed to use consumable: {}", e);
-                                return Err(e.into());
+                                e.screen
                             },
                         }
                     } else {
@@ -374,7 +384,7 @@ async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T
                             },
                             Err(e) => {
                                 error!("Failed to move consumable: {}", e);
-                                return Err(e.into());
+                                e.screen
                             },
                         }
                     } else {
@@ -396,7 +406,7 @@ async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T
                             },
                             Err(e) => {
                                 error!("Failed to sell consumable: {}", e);
-                                return Err(e.into());
+                                e.screen
                             },
                         }
                     } else {
@@ -408,7 +418,7 @@ async fn handle_consumable_management<'a, T: Hud<'a>>(mut screen: T) -> Result<T
                     screen
                 }
             },
-            Some("back") => return Ok(screen),
+            Some("back") => return screen,
             _ => {
                 println!("Invalid action. Use 'use <index>', 'move <from> <to>', 'sell <index>', or 'back'.");
                 screen
@@ -428,7 +438,7 @@ async fn main() {
     loop {
         info!("Waiting for connection");
         // Wait for a Game to connect
-        let mut balatro = match remotro.accept().await {
+        let mut balatro = match Session::start(&mut remotro).await {
             Ok(b) => {
                 info!("New connection accepted");
                 b
@@ -446,7 +456,7 @@ async fn main() {
                     CurrentScreen::Menu(menu) => {
                         display_menu();
                         if let Some(saved) = menu.saved_run() {
-                            println!("{}: Deck {:?}, Stake {:?}, Round {}, Ante {}", 
+                            println!("{}: Deck {}, Stake {}, Round {}, Ante {}", 
                                    "Saved run available".bright_green().bold(),
                                    saved.deck, saved.stake, saved.round, saved.ante);
                         }
@@ -454,8 +464,10 @@ async fn main() {
                         let action = get_string_input("Enter action:");
                         match action.trim().to_lowercase().as_str() {
                             "new" => {
-                                let deck: Deck = get_input("Select Deck (Red/Blue/Yellow/Green/Black/Magic/Nebula/Ghost/Abandoned/Checkered/Zodiac/Painted/Anaglyph/Plasma/Erratic):");
-                                let stake: Stake = get_input("Select Stake (White/Red/Green/Blue/Black/Purple/Orange/Gold):");
+                                let decks: Vec<String> = Deck::iter().map(|deck| deck.to_string()).collect();
+                                let deck: Deck = get_input(&format!("Select Deck ({}):", decks.join("/")));
+                                let stakes: Vec<String> = Stake::iter().map(|stake| stake.to_string()).collect();
+                                let stake: Stake = get_input(&format!("Select Stake ({}):", stakes.join("/")));
                                 match menu.new_run(deck, stake, None).await {
                                     Ok(_) => {},
                                     Err(e) => error!("Failed to start new run: {}", e),
@@ -515,7 +527,8 @@ async fn main() {
                         println!("\nBlind Options:");
                         println!("Small: {:?}", blinds.small());
                         println!("Big: {:?}", blinds.big());
-                        println!("Boss: {:?}", blinds.boss());
+                        let boss = blinds.boss();
+                        println!("Boss: {} - {} ({:?}, {} chips)", boss.kind.info().name, boss.kind.info().effect, boss.state, boss.chips);
                         
                         let action = get_string_input("Enter action:");
                         match action.trim().to_lowercase().as_str() {
@@ -532,12 +545,8 @@ async fn main() {
                                 }
                             },
                             "hud" => {
-                                match handle_hud_management(blinds).await {
-                                    Ok(_updated_blinds) => {
-                                        println!("Returned from HUD management");
-                                    },
-                                    Err(e) => error!("HUD management error: {}", e),
-                                }
+                                handle_hud_management(blinds).await;
+                                println!("Returned from HUD management");
                             },
                             _ => println!("Invalid action. Use 'select', 'skip', or 'hud'."),
                         }
@@ -603,9 +612,9 @@ async fn main() {
                         print_hud(&play);
                         
                         let input = get_string_input("Enter action:");
-                        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                        let parts: Vec<&str> = input.split_whitespace().collect();
                         
-                        match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                        match parts.first().map(|s| s.to_lowercase()).as_deref() {
                             Some("select") => {
                                 if parts.len() > 1 {
                                     let indices: Result<Vec<u32>, _> = parts[1..].iter()
@@ -686,12 +695,8 @@ async fn main() {
                                 }
                             },
                             Some("hud") => {
-                                match handle_hud_management(play).await {
-                                    Ok(_updated_play) => {
-                                        println!("Returned from HUD management");
-                                    },
-                                    Err(e) => error!("HUD management error: {}", e),
-                                }
+                                handle_hud_management(play).await;
+                                println!("Returned from HUD management");
                             },
                             _ => println!("Invalid action. Use 'select <indices>', 'play', 'discard', or 'hud'."),
                         }
@@ -705,9 +710,9 @@ async fn main() {
                         println!("Boosters: {:?}", shop.boosters());
                         
                         let input = get_string_input("Enter action:");
-                        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                        let parts: Vec<&str> = input.split_whitespace().collect();
                         
-                        match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                        match parts.first().map(|s| s.to_lowercase()).as_deref() {
                             Some("buy") => {
                                 if parts.len() == 3 {
                                     let item_type = parts[1].to_lowercase();
@@ -753,12 +758,8 @@ async fn main() {
                                 }
                             },
                             Some("hud") => {
-                                match handle_hud_management(shop).await {
-                                    Ok(_updated_shop) => {
-                                        println!("Returned from HUD management");
-                                    },
-                                    Err(e) => error!("HUD management error: {}", e),
-                                }
+                                handle_hud_management(shop).await;
+                                println!("Returned from HUD management");
                             },
                             _ => println!("Invalid action. Use 'buy <type> <index>', 'reroll', 'leave', or 'hud'."),
                         }
@@ -789,9 +790,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -843,12 +844,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'click <indices>', 'skip', or 'hud'."),
                                 }
@@ -860,9 +857,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -884,12 +881,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -901,9 +894,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -925,12 +918,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -957,9 +946,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1011,12 +1000,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'click <indices>', 'skip', or 'hud'."),
                                 }
@@ -1028,9 +1013,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1052,12 +1037,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -1090,9 +1071,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1144,12 +1125,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'click <indices>', 'skip', or 'hud'."),
                                 }
@@ -1161,9 +1138,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1185,12 +1162,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -1202,9 +1175,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1226,12 +1199,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -1258,9 +1227,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1312,12 +1281,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'click <indices>', 'skip', or 'hud'."),
                                 }
@@ -1329,9 +1294,9 @@ async fn main() {
                                 println!("Selections left: {:?}", pack.selections_left());
                                 
                                 let input = get_string_input("Enter action:");
-                                let parts: Vec<&str> = input.trim().split_whitespace().collect();
+                                let parts: Vec<&str> = input.split_whitespace().collect();
                                 
-                                match parts.get(0).map(|s| s.to_lowercase()).as_deref() {
+                                match parts.first().map(|s| s.to_lowercase()).as_deref() {
                                     Some("select") => {
                                         if parts.len() == 2 {
                                             if let Ok(index) = parts[1].parse::<u32>() {
@@ -1353,12 +1318,8 @@ async fn main() {
                                         }
                                     },
                                     Some("hud") => {
-                                        match handle_hud_management(pack).await {
-                                            Ok(_updated_pack) => {
-                                                println!("Returned from HUD management");
-                                            },
-                                            Err(e) => error!("HUD management error: {}", e),
-                                        }
+                                        handle_hud_management(pack).await;
+                                        println!("Returned from HUD management");
                                     },
                                     _ => println!("Invalid action. Use 'select <index>', 'skip', or 'hud'."),
                                 }
@@ -1366,6 +1327,14 @@ async fn main() {
                         }
                     }
                 },
+                Err(Error::Net(e)) => {
+                    error!("Connection Failed: {e}");
+                    info!("Waiting for the game to reconnect");
+                    if let Err(e) = balatro.resume(&mut remotro).await {
+                        error!("Could not resume session: {e}");
+                        break;
+                    }
+                }
                 Err(e) => {
                     error!("Connection Failed: {e}");
                     break;